@@ -1,4 +1,3 @@
-use libdt::layer::Layer;
 use libdt::layer::LinLayer;
 use libdt::layer::SigmaLayer;
 use libdt::network::Network;
@@ -7,7 +6,6 @@ use libdt::trainer::Trainer;
 use libdt::macros::neural_network;
 
 use nalgebra::DVector;
-use nalgebra::DMatrix;
 
 mod data;
 
@@ -1,75 +1,149 @@
 use proc_macro::TokenStream;
 use quote::quote;
+use quote::quote_spanned;
+use syn::spanned::Spanned;
 
 fn get_layers_and_ident(input: syn::DeriveInput) ->
-    (syn::Type, syn::Ident)
+    syn::Result<(syn::Type, syn::Ident)>
 {
     let ident = input.ident;
-    if let syn::Data::Struct(data) = input.data {
-        if let syn::Fields::Named(fields) = data.fields {
-            if fields.named.len() != 1 {
-                panic!("Could not construct neural \
-                        network: {} struct has too \
-                        many fields!", ident);
-            }
+    let data = match input.data {
+        syn::Data::Struct(data) => data,
+        _ => return Err(syn::Error::new_spanned(
+            &ident,
+            format!("Could not construct neural network: \
+                     {} is not a struct!", ident))),
+    };
 
-            let layers_field = fields.named
-                .into_iter().next().unwrap();
-            let field_ident = layers_field.ident;
-            if field_ident.is_none() {
-                panic!("Could not construct neural network: \
-                        {} struct does not have `layers` \
-                        field!", ident);
-            }
-            let fieldname = field_ident
-                .unwrap().to_string();
+    let fields = match data.fields {
+        syn::Fields::Named(fields) => fields,
+        fields => return Err(syn::Error::new_spanned(
+            fields,
+            format!("Could not construct neural network: \
+                     {} struct does not have `layers` \
+                     field!", ident))),
+    };
 
-            if String::from("layers") != fieldname {
-                panic!("Could not construct neural network: \
-                        {} struct does not have `layers` \
-                        field!", ident);
-            }
+    if fields.named.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &fields.named[1],
+            format!("Could not construct neural \
+                     network: {} struct has too \
+                     many fields!", ident)));
+    }
 
-            return (layers_field.ty, ident);
-        } else {
-            panic!("Could not construct neural network: \
-                    {} struct does not have `layers` \
-                    field!", ident);
+    let layers_field = match fields.named.into_iter().next() {
+        Some(field) => field,
+        None => return Err(syn::Error::new_spanned(
+            &ident,
+            format!("Could not construct neural network: \
+                     {} struct does not have `layers` \
+                     field!", ident))),
+    };
+
+    if layers_field.ident.as_ref().is_none_or(|i| i != "layers") {
+        return Err(syn::Error::new_spanned(
+            &layers_field.ident,
+            format!("Could not construct neural network: \
+                     {} struct does not have `layers` \
+                     field!", ident)));
+    }
+
+    Ok((layers_field.ty, ident))
+}
+
+/// Writes generic arguments of every path segment with `::`,
+/// so that layer types can be used in expressions too.
+fn turbofish(mut path: syn::Path) -> syn::Path {
+    for segment in path.segments.iter_mut() {
+        if let syn::PathArguments::AngleBracketed(args) =
+            &mut segment.arguments
+        {
+            args.colon2_token = Some(Default::default());
         }
-    } else {
-        panic!("Could not construct neural network: \
-                {} is not a struct!", ident);
     }
+
+    path
 }
 
 #[proc_macro_attribute]
 pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input: syn::DeriveInput =
-        syn::parse(item.clone()).unwrap();
-    let (ty, ident) = get_layers_and_ident(input);
-
-    let layers = if let syn::Type::Tuple(layers) = ty {
-        layers.elems
-    } else {
-        panic!("Could not construct neural network: \
-                `layers` is not a tuple!");
+    let input: syn::DeriveInput = match syn::parse(item.clone()) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut output =
+        proc_macro2::TokenStream::from(item);
+    output.extend(match network_impl(input) {
+        Ok(network_impl) => network_impl,
+        Err(err) => err.to_compile_error(),
+    });
+
+    proc_macro::TokenStream::from(output)
+}
+
+/// Derive counterpart of `#[neural_network]`.
+#[proc_macro_derive(Network)]
+pub fn derive_network(item: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = match syn::parse(item) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match network_impl(input) {
+        Ok(network_impl) => network_impl.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Generates `Network` implementation and dimension checks
+/// for network struct.
+fn network_impl(input: syn::DeriveInput) ->
+    syn::Result<proc_macro2::TokenStream>
+{
+    let generics = input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) =
+        generics.split_for_impl();
+    let (ty, ident) = get_layers_and_ident(input)?;
+
+    let layers = match ty {
+        syn::Type::Tuple(layers) if layers.elems.is_empty() =>
+            return Err(syn::Error::new_spanned(
+                layers,
+                "Could not construct neural network: \
+                 `layers` tuple is empty!")),
+        syn::Type::Tuple(layers) => layers.elems,
+        ty => return Err(syn::Error::new_spanned(
+            ty,
+            "Could not construct neural network: \
+             `layers` is not a tuple!")),
     };
 
     let mut layer_idents: Vec<syn::Path> = Vec::new();
     for layer in layers.into_iter() {
         layer_idents.push(match layer {
-            syn::Type::Path(path) => path.path,
-            _ => panic!("Could not construct neural network: \
-                    Invalid layer type!"),
+            syn::Type::Path(path) => turbofish(path.path),
+            layer => return Err(syn::Error::new_spanned(
+                layer,
+                "Could not construct neural network: \
+                 Invalid layer type!")),
         });
     }
     
     let layer_idents: Vec<syn::Path> = layer_idents
         .into_iter().collect();
 
-    let first_layer = &layer_idents[0];
-    let last_layer = &layer_idents[
-        layer_idents.len()-1];
+    // Layers are referred to by fully qualified paths, so that
+    // declaring a network does not require any imports.
+    let layer_types: Vec<proc_macro2::TokenStream> = layer_idents
+        .iter()
+        .map(|layer| quote!{<#layer as ::libdt::layer::Layer>})
+        .collect();
+
+    let first_layer = &layer_types[0];
+    let last_layer = &layer_types[
+        layer_types.len()-1];
 
     let mut new_list = proc_macro2::TokenStream::new();
     for layer_ident in layer_idents.iter() {
@@ -90,11 +164,52 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
     layers_string += "]";
 
+    // Dimensions of neighbouring layers are checked at compile
+    // time, pointing at the second layer of the mismatched pair.
+    let mut dimension_asserts = proc_macro2::TokenStream::new();
+    for i in 1..layer_idents.len() {
+        let prev_layer = &layer_idents[i-1];
+        let layer = &layer_idents[i];
+        let prev_layer_type = &layer_types[i-1];
+        let layer_type = &layer_types[i];
+        let message = format!(
+            "Could not construct neural network: NEURONS_OUT of \
+             layer {} (`{}`) does not match NEURONS_IN of \
+             layer {} (`{}`)!",
+            i-1, quote!(#prev_layer).to_string().replace(' ', ""),
+            i, quote!(#layer).to_string().replace(' ', ""));
+
+        dimension_asserts.extend(quote_spanned!{layer.span()=>
+            assert!(#prev_layer_type::NEURONS_OUT ==
+                    #layer_type::NEURONS_IN,
+                    #message);
+        });
+    }
+
+    // Generic parameters can not be used in free constants, so
+    // generic networks check dimensions in an associated constant
+    // instead, once it is evaluated for concrete parameters.
+    let (check_dimensions, use_dimensions_check) =
+        if generics.params.is_empty() {
+            (quote!{
+                const _: () = { #dimension_asserts };
+            }, quote!{})
+        } else {
+            (quote!{
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    #[doc(hidden)]
+                    const __CHECK_DIMENSIONS: () = { #dimension_asserts };
+                }
+            }, quote!{
+                let () = Self::__CHECK_DIMENSIONS;
+            })
+        };
+
     let mut params_cnt_sum = proc_macro2::TokenStream::new();
-    for i in 0..layer_idents.len() {
-        let layer_ident = &layer_idents[i];
+    for i in 0..layer_types.len() {
+        let layer_ident = &layer_types[i];
         params_cnt_sum.extend(quote!{#layer_ident::PARAMS_CNT});
-        if i < layer_idents.len()-1 {
+        if i < layer_types.len()-1 {
             params_cnt_sum.extend(quote!{ + });
         }
     }
@@ -103,7 +218,7 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
         Vec::with_capacity(layer_idents.len()+1);
     d_offsets.push(quote!{0});
     for i in 0..layer_idents.len() {
-        let layer_ident = &layer_idents[i];
+        let layer_ident = &layer_types[i];
         d_offsets.push(d_offsets[i].clone());
         d_offsets[i+1].extend(quote!{
             + #layer_ident::PARAMS_CNT
@@ -112,7 +227,7 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut eval_all_layers = proc_macro2::TokenStream::new();
     for i in 0..layer_idents.len() {
-        let layer_ident = &layer_idents[i];
+        let layer_ident = &layer_types[i];
 
         let old_offset = &d_offsets[i];
         let offset = &d_offsets[i+1];
@@ -132,26 +247,26 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
         let idx: syn::Index = i.into();
 
         forward_all_layers.extend(quote!{
-            let x = self.layers.#idx.forward(
-                &p[#old_offset..#offset], x);
+            let x = ::libdt::layer::Layer::forward(
+                &mut self.layers.#idx, &p[#old_offset..#offset], x);
         });
         backward_all_layers.extend(quote!{
-            self.layers.#idx.backward(
-                &p[#old_offset..#offset]);
+            ::libdt::layer::Layer::backward(
+                &mut self.layers.#idx, &p[#old_offset..#offset]);
         });
     }
 
     let mut compute_jacobian = proc_macro2::TokenStream::new();
     compute_jacobian.extend(quote!{
-        let mut jm: DMatrix<f64> =
-            DMatrix::from_element_generic(
-                nalgebra::base::dimension::Dyn(Self::NEURONS_OUT),
-                nalgebra::base::dimension::Dyn(Self::PARAMS_CNT), 0f64);
-        let m: DMatrix<f64> = {
-            let mut m: DMatrix<f64> =
-                DMatrix::from_element_generic(
-                    nalgebra::base::dimension::Dyn(Self::NEURONS_OUT),
-                    nalgebra::base::dimension::Dyn(Self::NEURONS_OUT), 0f64);
+        let mut jm: ::nalgebra::DMatrix<f64> =
+            ::nalgebra::DMatrix::from_element_generic(
+                ::nalgebra::base::dimension::Dyn(Self::NEURONS_OUT),
+                ::nalgebra::base::dimension::Dyn(Self::PARAMS_CNT), 0f64);
+        let m: ::nalgebra::DMatrix<f64> = {
+            let mut m: ::nalgebra::DMatrix<f64> =
+                ::nalgebra::DMatrix::from_element_generic(
+                    ::nalgebra::base::dimension::Dyn(Self::NEURONS_OUT),
+                    ::nalgebra::base::dimension::Dyn(Self::NEURONS_OUT), 0f64);
             m.fill_diagonal(1f64);
 
             m
@@ -160,23 +275,25 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
     });
 
     for i in (1..layer_idents.len()).rev() {
-        let layer_ident = &layer_idents[i];
+        let layer_ident = &layer_types[i];
         let idx: syn::Index = i.into();
         let prev_idx: syn::Index = (i-1).into();
         compute_jacobian.extend(quote!{
-            let jf = &m * self.layers.#idx.chain_end(
-                    &self.layers.#prev_idx.signal);
+            let jf = &m * ::libdt::layer::Layer::chain_end(
+                    &self.layers.#idx, &self.layers.#prev_idx.signal);
             offset -= #layer_ident::PARAMS_CNT;
             for i in offset..offset+#layer_ident::PARAMS_CNT {
                 jm.set_column(i, &jf.index((.., i - offset)));
             }
             
-            let m = m * self.layers.#idx.chain_element();
+            let m = m * ::libdt::layer::Layer::chain_element(
+                    &self.layers.#idx);
         });
     }
     let idx: syn::Index = 0.into();
     compute_jacobian.extend(quote!{
-        let jf = m * self.layers.#idx.chain_end(&x);
+        let jf = m * ::libdt::layer::Layer::chain_end(
+                &self.layers.#idx, &x);
         for i in 0..#first_layer::PARAMS_CNT {
             jm.set_column(i, &jf.index((.., i)));
         }
@@ -184,7 +301,7 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
     let mut extend_by_initial_params = proc_macro2::TokenStream::new();
     for i in 0..layer_idents.len() {
-        let layer_ident = &layer_idents[i];
+        let layer_ident = &layer_types[i];
 
         extend_by_initial_params.extend(quote!{
             p.append(&mut #layer_ident::default_initial_params());
@@ -192,12 +309,16 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     let network_trait_impl = quote! {
-        impl Network for #ident {
+        impl #impl_generics ::libdt::network::Network
+            for #ident #ty_generics #where_clause
+        {
             const PARAMS_CNT: usize = #params_cnt_sum;
             const NEURONS_IN: usize = #first_layer::NEURONS_IN;
             const NEURONS_OUT: usize = #last_layer::NEURONS_OUT;
 
             fn new() -> Self {
+                #use_dimensions_check
+
                 Self {
                     layers: (#new_list),
                 }
@@ -207,9 +328,11 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 #layers_string
             }
 
-            fn eval(p: &[f64], x: DVector<f64>) ->
-                DVector<f64>
+            fn eval(p: &[f64], x: ::nalgebra::DVector<f64>) ->
+                ::nalgebra::DVector<f64>
             {
+                #use_dimensions_check
+
                 assert_eq!(p.len(), Self::PARAMS_CNT);
                 assert_eq!(x.len(), Self::NEURONS_IN);
 
@@ -218,8 +341,9 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 x
             }
 
-            fn forward(&mut self, p: &[f64], x: DVector<f64>) ->
-                DVector<f64>
+            fn forward(&mut self, p: &[f64],
+                       x: ::nalgebra::DVector<f64>) ->
+                ::nalgebra::DVector<f64>
             {
                 assert_eq!(p.len(), Self::PARAMS_CNT);
                 assert_eq!(x.len(), Self::NEURONS_IN);
@@ -236,8 +360,8 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 #backward_all_layers
             }
 
-            fn jacobian(&mut self, x: &DVector<f64>) ->
-                DMatrix<f64>
+            fn jacobian(&mut self, x: &::nalgebra::DVector<f64>) ->
+                ::nalgebra::DMatrix<f64>
             {
                 assert_eq!(x.len(), Self::NEURONS_IN);
 
@@ -257,9 +381,8 @@ pub fn neural_network(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
-    let mut output =
-        proc_macro2::TokenStream::from(item);
-    output.extend(network_trait_impl);
+    let mut output = network_trait_impl;
+    output.extend(check_dimensions);
 
-    proc_macro::TokenStream::from(output)
+    Ok(output)
 }
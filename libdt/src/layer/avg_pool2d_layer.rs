@@ -0,0 +1,181 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Two dimensional average pooling layer.
+///
+/// Takes mean over non-overlapping `POOL` x `POOL` windows
+/// of each of `CH` images of size `HEIGHT` x `WIDTH`. Layout
+/// of the signal is the same as in `Conv2DLayer`. Rows and columns
+/// which do not fill a whole window are dropped.
+pub struct AvgPool2DLayer<const HEIGHT: usize,
+                          const WIDTH: usize,
+                          const CH: usize,
+                          const POOL: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> AvgPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    const OUT_HEIGHT: usize = HEIGHT / POOL;
+    const OUT_WIDTH: usize = WIDTH / POOL;
+
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Calls `f(output, input)` for every input in window of each output.
+    fn for_each_window<F>(mut f: F)
+        where F: FnMut(usize, usize)
+    {
+        for c in 0..CH {
+            for r in 0..Self::OUT_HEIGHT*POOL {
+                for col in 0..Self::OUT_WIDTH*POOL {
+                    let out = (c*Self::OUT_HEIGHT + r / POOL)*Self::OUT_WIDTH +
+                              col / POOL;
+                    f(out, (c*HEIGHT + r)*WIDTH + col);
+                }
+            }
+        }
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> Default
+    for AvgPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> Layer
+    for AvgPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = CH * HEIGHT * WIDTH;
+    const NEURONS_OUT: usize = CH * (HEIGHT / POOL) * (WIDTH / POOL);
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let scale = 1. / (POOL*POOL) as f64;
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        Self::for_each_window(|out, inp| {
+            y[out] += scale*x[inp];
+        });
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        let scale = 1. / (POOL*POOL) as f64;
+        let chain_element = &mut self.chain_element;
+        Self::for_each_window(|out, inp| {
+            chain_element[(out, inp)] = scale;
+        });
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 0f64,
+                        4f64, 3f64, 2f64, 1f64].as_slice());
+        
+        let y = AvgPool2DLayer::<2, 4, 1, 2>::eval(&[], x);
+        assert_eq!(y, na::vector![2.5f64, 1.5f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64,
+                        -1f64, -2f64, -3f64, -4f64].as_slice());
+        let mut layer = AvgPool2DLayer::<2, 2, 2, 2>::new();
+        
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![2.5f64, -2.5f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 0f64,
+                        4f64, 3f64, 3f64, 1f64].as_slice());
+        let mut layer = AvgPool2DLayer::<2, 4, 1, 2>::new();
+        
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![0.25f64, 0.25f64, 0f64, 0f64, 0.25f64, 0.25f64, 0f64, 0f64;
+                               0f64, 0f64, 0.25f64, 0.25f64, 0f64, 0f64, 0.25f64, 0.25f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64].as_slice());
+        let layer = AvgPool2DLayer::<2, 2, 1, 2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
@@ -0,0 +1,176 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Sequential composition of two layers.
+///
+/// Computes `b(a(x))`. Parameters of `a` are followed by parameters
+/// of `b`. Chains can be nested to build sub-chains of any length,
+/// which are usable wherever single layer is expected.
+pub struct Chain<A: Layer, B: Layer> {
+    a: A,
+    b: B,
+    hidden: Vector::<f64, dim::Dyn,
+                     VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<A: Layer + Default, B: Layer + Default> Chain<A, B> {
+    pub fn new() -> Self {
+        const {
+            assert!(A::NEURONS_OUT == B::NEURONS_IN,
+                    "Could not construct chain: NEURONS_OUT of first \
+                     layer does not match NEURONS_IN of second layer!");
+        }
+
+        Self {
+            a: A::default(),
+            b: B::default(),
+            hidden: Vector::from_element_generic(dim::Dyn(A::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<A: Layer + Default, B: Layer + Default> Default for Chain<A, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Layer, B: Layer> Layer for Chain<A, B> {
+    const PARAMS_CNT: usize = A::PARAMS_CNT + B::PARAMS_CNT;
+    const NEURONS_IN: usize = A::NEURONS_IN;
+    const NEURONS_OUT: usize = B::NEURONS_OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+
+        B::eval_unchecked(pb, A::eval_unchecked(pa, x))
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+
+        B::eval(pb, A::eval(pa, x))
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+        self.hidden = self.a.forward(pa, x);
+        self.signal = self.b.forward(pb, self.hidden.clone());
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+        self.a.backward(pa);
+        self.b.backward(pb);
+
+        self.chain_element =
+            self.b.chain_element() * self.a.chain_element();
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        matrix.columns_mut(0, A::PARAMS_CNT).copy_from(
+            &(self.b.chain_element() * self.a.chain_end(x)));
+        matrix.columns_mut(A::PARAMS_CNT, B::PARAMS_CNT).copy_from(
+            &self.b.chain_end(&self.hidden));
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut p: Vec<f64> = A::default_initial_params();
+        p.append(&mut B::default_initial_params());
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::LinLayer;
+    use super::super::ReLULayer;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 7] = [1., -1., 0., 0., 2., 1., 1.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+
+        let y = Chain::<LinLayer<1, 2>, LinLayer<2, 1>>::eval(&p, x);
+        assert_eq!(y, na::vector![4f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 4] = [1., -1., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let mut layer = Chain::<LinLayer<1, 2>, ReLULayer<2>>::new();
+
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![3f64, 0f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 4] = [1., -1., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let mut layer = Chain::<LinLayer<1, 2>, ReLULayer<2>>::new();
+
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![1f64;
+                               0f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p: [f64; 7] = [1., -1., 0., 0., 2., 1., 1.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let mut layer = Chain::<LinLayer<1, 2>, LinLayer<2, 1>>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![6f64, 3f64, 2f64, 1f64, 3f64, -3f64, 1f64]);
+    }
+}
@@ -0,0 +1,188 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Chebyshev polynomial feature expansion.
+///
+/// Every input `x_i` is expanded into `T_1(x_i), ..., T_DEGREE(x_i)`,
+/// laid out input after input. Chebyshev polynomials are well
+/// conditioned on `[-1, 1]`, so inputs should be scaled there first.
+/// `StandardizeLayer` only gives inputs zero mean and unit variance,
+/// which does not bound them; follow it with `TanhLayer` to keep
+/// them within `[-1, 1]`.
+pub struct ChebyshevLayer<const IN: usize, const DEGREE: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const DEGREE: usize> ChebyshevLayer<IN, DEGREE>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Returns `T_1(x), ..., T_DEGREE(x)` and their derivatives,
+    /// using `T_{n+1} = 2x T_n - T_{n-1}` and `T_n' = n U_{n-1}`,
+    /// where `U_{n+1} = 2x U_n - U_{n-1}`.
+    fn polynomials(x: f64) -> (Vec<f64>, Vec<f64>) {
+        let mut t: Vec<f64> = Vec::with_capacity(DEGREE);
+        let mut dt: Vec<f64> = Vec::with_capacity(DEGREE);
+
+        let (mut t_prev, mut t_cur) = (1f64, x);
+        let (mut u_prev, mut u_cur) = (0f64, 1f64);
+        for n in 1..=DEGREE {
+            t.push(t_cur);
+            dt.push(n as f64 * u_cur);
+
+            (t_prev, t_cur) = (t_cur, 2.*x*t_cur - t_prev);
+            (u_prev, u_cur) = (u_cur, 2.*x*u_cur - u_prev);
+        }
+
+        (t, dt)
+    }
+}
+
+impl<const IN: usize, const DEGREE: usize> Default
+    for ChebyshevLayer<IN, DEGREE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const DEGREE: usize> Layer
+    for ChebyshevLayer<IN, DEGREE>
+{
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = IN * DEGREE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        for (i, xi) in x.iter().enumerate() {
+            let (t, _) = Self::polynomials(*xi);
+            y.rows_mut(i*DEGREE, DEGREE).copy_from_slice(&t);
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for i in 0..IN {
+            let (_, dt) = Self::polynomials(self.input[i]);
+            for (d, dti) in dt.iter().enumerate() {
+                self.chain_element[(i*DEGREE + d, i)] = *dti;
+            }
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![0.5f64, -1f64].as_slice());
+
+        let y = ChebyshevLayer::<2, 3>::eval(&[], x);
+        assert_eq!(y, na::vector![0.5f64, -0.5f64, -1f64,
+                                  -1f64, 1f64, -1f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![2f64].as_slice());
+        let mut layer = ChebyshevLayer::<1, 4>::new();
+
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![2f64, 7f64, 26f64, 97f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![0.3f64, -0.8f64].as_slice());
+        let mut layer = ChebyshevLayer::<2, 4>::new();
+
+        let _ = layer.forward(&[], x.clone());
+        layer.backward(&[]);
+
+        const H: f64 = 0.000_001;
+        for j in 0..2 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (ChebyshevLayer::<2, 4>::eval(&[], x1) -
+                     ChebyshevLayer::<2, 4>::eval(&[], x2)) / (2.*H);
+            for i in 0..8 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![0.5f64].as_slice());
+        let layer = ChebyshevLayer::<1, 3>::new();
+
+        assert_eq!(layer.chain_end(&x).shape(), (3, 0));
+    }
+}
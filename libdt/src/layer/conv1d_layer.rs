@@ -0,0 +1,238 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// One dimensional convolution layer.
+///
+/// Input signal is `CH_IN` channels of length `LEN` stored one
+/// channel after another, output is `CH_OUT` channels of length
+/// `(LEN + 2*PADDING - KERNEL) / STRIDE + 1` stored the same way.
+/// Padding is filled with zeros.
+///
+/// Parameters are kernel weights `w[o][c][k]` stored at
+/// `(o*CH_IN + c)*KERNEL + k`, followed by `CH_OUT` biases.
+pub struct Conv1DLayer<const LEN: usize,
+                       const CH_IN: usize,
+                       const CH_OUT: usize,
+                       const KERNEL: usize,
+                       const STRIDE: usize = 1,
+                       const PADDING: usize = 0> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const LEN: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize>
+    Conv1DLayer<LEN, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    const OUT_LEN: usize = (LEN + 2*PADDING - KERNEL) / STRIDE + 1;
+    const WEIGHTS_CNT: usize = CH_OUT * CH_IN * KERNEL;
+
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Position in input channel seen by kernel tap `k`
+    /// at output position `t`, `None` if it falls into padding.
+    fn input_pos(t: usize, k: usize) -> Option<usize> {
+        (t*STRIDE + k).checked_sub(PADDING)
+            .filter(|j| *j < LEN)
+    }
+
+    fn weight_idx(o: usize, c: usize, k: usize) -> usize {
+        (o*CH_IN + c)*KERNEL + k
+    }
+}
+
+impl<const LEN: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize> Default
+    for Conv1DLayer<LEN, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const LEN: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize> Layer
+    for Conv1DLayer<LEN, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    const PARAMS_CNT: usize = CH_OUT * CH_IN * KERNEL + CH_OUT;
+    const NEURONS_IN: usize = CH_IN * LEN;
+    const NEURONS_OUT: usize = CH_OUT * ((LEN + 2*PADDING - KERNEL) / STRIDE + 1);
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        for o in 0..CH_OUT {
+            for t in 0..Self::OUT_LEN {
+                let mut sum = p[Self::WEIGHTS_CNT + o];
+                for c in 0..CH_IN {
+                    for k in 0..KERNEL {
+                        if let Some(j) = Self::input_pos(t, k) {
+                            sum += p[Self::weight_idx(o, c, k)] *
+                                   x[c*LEN + j];
+                        }
+                    }
+                }
+                y[o*Self::OUT_LEN + t] = sum;
+            }
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for o in 0..CH_OUT {
+            for t in 0..Self::OUT_LEN {
+                for c in 0..CH_IN {
+                    for k in 0..KERNEL {
+                        if let Some(j) = Self::input_pos(t, k) {
+                            self.chain_element[(o*Self::OUT_LEN + t,
+                                                c*LEN + j)] +=
+                                p[Self::weight_idx(o, c, k)];
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for o in 0..CH_OUT {
+            for t in 0..Self::OUT_LEN {
+                let row = o*Self::OUT_LEN + t;
+                for c in 0..CH_IN {
+                    for k in 0..KERNEL {
+                        if let Some(j) = Self::input_pos(t, k) {
+                            matrix[(row, Self::weight_idx(o, c, k))] =
+                                x[c*LEN + j];
+                        }
+                    }
+                }
+                matrix[(row, Self::WEIGHTS_CNT + o)] = 1.;
+            }
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 3] = [1., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 4f64, 8f64].as_slice());
+        
+        let y = Conv1DLayer::<4, 1, 1, 2>::eval(&p, x);
+        assert_eq!(y, na::vector![-0.5, -1.5, -3.5]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 3] = [1., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 4f64, 8f64].as_slice());
+        let mut layer = Conv1DLayer::<4, 1, 1, 2, 2, 1>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![-0.5, -1.5, 8.5]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 3] = [1., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 4f64, 8f64].as_slice());
+        let mut layer = Conv1DLayer::<4, 1, 1, 2, 2, 1>::new();
+        
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![-1., 0., 0., 0.;
+                               0., 1., -1., 0.;
+                               0., 0., 0., 1.]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64].as_slice());
+        let layer = Conv1DLayer::<2, 2, 2, 2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![1., 2., 3., 4., 0., 0., 0., 0., 1., 0.;
+                               0., 0., 0., 0., 1., 2., 3., 4., 0., 1.]);
+    }
+}
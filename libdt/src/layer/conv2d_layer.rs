@@ -0,0 +1,255 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Two dimensional convolution layer with square kernel.
+///
+/// Input signal is `CH_IN` images of size `HEIGHT` x `WIDTH`
+/// stored one channel after another, each image row by row.
+/// Output is `CH_OUT` images of size
+/// `((HEIGHT + 2*PADDING - KERNEL) / STRIDE + 1)` x
+/// `((WIDTH + 2*PADDING - KERNEL) / STRIDE + 1)` stored the same way.
+/// Padding is filled with zeros.
+///
+/// Parameters are kernel weights `w[o][c][kr][kc]` stored at
+/// `((o*CH_IN + c)*KERNEL + kr)*KERNEL + kc`, followed by `CH_OUT` biases.
+pub struct Conv2DLayer<const HEIGHT: usize,
+                       const WIDTH: usize,
+                       const CH_IN: usize,
+                       const CH_OUT: usize,
+                       const KERNEL: usize,
+                       const STRIDE: usize = 1,
+                       const PADDING: usize = 0> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize>
+    Conv2DLayer<HEIGHT, WIDTH, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    const OUT_HEIGHT: usize = (HEIGHT + 2*PADDING - KERNEL) / STRIDE + 1;
+    const OUT_WIDTH: usize = (WIDTH + 2*PADDING - KERNEL) / STRIDE + 1;
+    const WEIGHTS_CNT: usize = CH_OUT * CH_IN * KERNEL * KERNEL;
+
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Position in input dimension of size `len` seen by kernel
+    /// tap `k` at output position `t`, `None` if it falls into padding.
+    fn input_pos(t: usize, k: usize, len: usize) -> Option<usize> {
+        (t*STRIDE + k).checked_sub(PADDING)
+            .filter(|j| *j < len)
+    }
+
+    fn weight_idx(o: usize, c: usize, kr: usize, kc: usize) -> usize {
+        ((o*CH_IN + c)*KERNEL + kr)*KERNEL + kc
+    }
+
+    /// Calls `f(output, input, weight)` for every pair of output
+    /// and input neurons connected by kernel weight.
+    fn for_each_tap<F>(mut f: F)
+        where F: FnMut(usize, usize, usize)
+    {
+        for o in 0..CH_OUT {
+            for tr in 0..Self::OUT_HEIGHT {
+                for tc in 0..Self::OUT_WIDTH {
+                    let out = (o*Self::OUT_HEIGHT + tr)*Self::OUT_WIDTH + tc;
+                    for c in 0..CH_IN {
+                        for kr in 0..KERNEL {
+                            let Some(r) = Self::input_pos(tr, kr, HEIGHT)
+                                else { continue };
+                            for kc in 0..KERNEL {
+                                let Some(col) = Self::input_pos(tc, kc, WIDTH)
+                                    else { continue };
+                                f(out, (c*HEIGHT + r)*WIDTH + col,
+                                  Self::weight_idx(o, c, kr, kc));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize> Default
+    for Conv2DLayer<HEIGHT, WIDTH, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH_IN: usize,
+     const CH_OUT: usize,
+     const KERNEL: usize,
+     const STRIDE: usize,
+     const PADDING: usize> Layer
+    for Conv2DLayer<HEIGHT, WIDTH, CH_IN, CH_OUT, KERNEL, STRIDE, PADDING>
+{
+    const PARAMS_CNT: usize = CH_OUT * CH_IN * KERNEL * KERNEL + CH_OUT;
+    const NEURONS_IN: usize = CH_IN * HEIGHT * WIDTH;
+    const NEURONS_OUT: usize = CH_OUT *
+        ((HEIGHT + 2*PADDING - KERNEL) / STRIDE + 1) *
+        ((WIDTH + 2*PADDING - KERNEL) / STRIDE + 1);
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let plane = Self::OUT_HEIGHT * Self::OUT_WIDTH;
+        let mut y: DVector<f64> = DVector::from_fn_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1,
+            |i, _| p[Self::WEIGHTS_CNT + i / plane]);
+        Self::for_each_tap(|out, inp, w| {
+            y[out] += p[w] * x[inp];
+        });
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        let chain_element = &mut self.chain_element;
+        Self::for_each_tap(|out, inp, w| {
+            chain_element[(out, inp)] += p[w];
+        });
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        Self::for_each_tap(|out, inp, w| {
+            matrix[(out, w)] = x[inp];
+        });
+        let plane = Self::OUT_HEIGHT * Self::OUT_WIDTH;
+        for out in 0..Self::NEURONS_OUT {
+            matrix[(out, Self::WEIGHTS_CNT + out / plane)] = 1.;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 5] = [1., 0., 0., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64,
+                        4f64, 5f64, 6f64,
+                        7f64, 8f64, 9f64].as_slice());
+        
+        let y = Conv2DLayer::<3, 3, 1, 1, 2>::eval(&p, x);
+        assert_eq!(y, na::vector![-3.5, -3.5, -3.5, -3.5]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 5] = [1., 0., 0., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64,
+                        4f64, 5f64, 6f64,
+                        7f64, 8f64, 9f64].as_slice());
+        let mut layer = Conv2DLayer::<3, 3, 1, 1, 2, 2, 1>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![-0.5, -2.5, -6.5, -3.5]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 5] = [1., 0., 0., -1., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64,
+                        4f64, 5f64, 6f64,
+                        7f64, 8f64, 9f64].as_slice());
+        let mut layer = Conv2DLayer::<3, 3, 1, 1, 2, 2, 1>::new();
+        
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![-1., 0., 0., 0., 0., 0., 0., 0., 0.;
+                               0., 0., -1., 0., 0., 0., 0., 0., 0.;
+                               0., 0., 0., 0., 0., 0., -1., 0., 0.;
+                               0., 0., 0., 0., 1., 0., 0., 0., -1.]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64].as_slice());
+        let layer = Conv2DLayer::<2, 2, 1, 2, 2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![1., 2., 3., 4., 0., 0., 0., 0., 1., 0.;
+                               0., 0., 0., 0., 1., 2., 3., 4., 0., 1.]);
+    }
+}
@@ -0,0 +1,168 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::Layer;
+
+/// Dropout layer.
+///
+/// During training (`forward`) every neuron is zeroed with probability
+/// `rate` and the remaining ones are scaled by `1 / (1 - rate)`, so
+/// `eval` is the deterministic expectation, which is identity.
+pub struct DropoutLayer<const SIZE: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    mask: Vector::<f64, dim::Dyn,
+                   VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    rate: f64,
+    rng: StdRng,
+}
+
+impl<const SIZE: usize> DropoutLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            mask: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                               dim::U1, 1f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            rate: 0.5f64,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets probability of dropping a neuron.
+    pub fn set_rate(&mut self, rate: f64) {
+        assert!((0f64..1f64).contains(&rate));
+
+        self.rate = rate;
+    }
+
+    /// Reseeds random number generator, making
+    /// following `forward` calls reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl<const SIZE: usize> Default for DropoutLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for DropoutLayer<SIZE> {
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let scale = 1. / (1. - self.rate);
+        for mi in self.mask.iter_mut() {
+            *mi = if self.rng.gen_bool(self.rate) { 0. } else { scale };
+        }
+
+        self.signal = Self::eval(p, x).component_mul(&self.mask);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_diagonal(&self.mask);
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        
+        let y = DropoutLayer::<2>::eval(&[], x);
+        assert_eq!(y, na::vector![1f64, 2f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(&[1f64; 1000]);
+        let mut layer = DropoutLayer::<1000>::new();
+        layer.set_rate(0.2);
+        layer.seed(7);
+        
+        let y = layer.forward(&[], x.clone());
+        let dropped = y.iter().filter(|yi| **yi == 0.).count();
+        assert!(dropped > 150 && dropped < 250);
+        assert!(y.iter().all(|yi| *yi == 0. || *yi == 1.25));
+
+        layer.seed(7);
+        assert_eq!(layer.forward(&[], x), y);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64].as_slice());
+        let mut layer = DropoutLayer::<4>::new();
+        layer.seed(3);
+        
+        let y = layer.forward(&[], x.clone());
+        layer.backward(&[]);
+
+        let chain_element = layer.chain_element();
+        assert_eq!(chain_element * x, y);
+        assert_eq!(*chain_element,
+                   DMatrix::from_diagonal(&chain_element.diagonal()));
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let layer = DropoutLayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
@@ -0,0 +1,248 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::recurrent::Gate;
+use super::recurrent::Step;
+use super::recurrent::unroll;
+
+/// Elman (simple recurrent) layer unrolled over `STEPS` time steps.
+///
+/// Input is the sequence `x_1, ..., x_STEPS` concatenated into one
+/// vector, output is the sequence of hidden states `h_1, ..., h_STEPS`
+/// concatenated the same way, where
+/// `h_t = tanh(W x_t + U h_{t-1} + b)` and `h_0 = 0`.
+///
+/// Parameters are `W` (`HIDDEN x IN`, column-major), `U`
+/// (`HIDDEN x HIDDEN`, column-major) and `b`, in that order. Since
+/// they are shared by all time steps, `chain_end` and `chain_element`
+/// accumulate derivatives through the whole unrolled sequence.
+pub struct ElmanLayer<const IN: usize,
+                      const HIDDEN: usize,
+                      const STEPS: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> ElmanLayer<IN, HIDDEN, STEPS>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    fn cell(p: &[f64], x: &DVector<f64>, h: &DVector<f64>) -> DVector<f64> {
+        let g = Gate::nth(p, 0, IN, HIDDEN);
+
+        (g.input_part(x) + g.recurrent_part(h)).map(f64::tanh)
+    }
+
+    fn step(p: &[f64], x: &DVector<f64>, h: &DVector<f64>) -> Step {
+        let g = Gate::nth(p, 0, IN, HIDDEN);
+
+        // Derivative of `tanh` expressed by its value.
+        let d = Self::cell(p, x, h).map(|h| 1. - h*h);
+
+        let mut params: DMatrix<f64> =
+            DMatrix::zeros(HIDDEN, Self::PARAMS_CNT);
+        g.add_params_derivative(&mut params, 0, &d, &d, x, h);
+
+        Step {
+            input: g.input_derivative(&d),
+            state: g.recurrent_derivative(&d),
+            params,
+        }
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Default for ElmanLayer<IN, HIDDEN, STEPS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Layer for ElmanLayer<IN, HIDDEN, STEPS>
+{
+    const PARAMS_CNT: usize = Gate::params_cnt(IN, HIDDEN);
+    const NEURONS_IN: usize = IN * STEPS;
+    const NEURONS_OUT: usize = HIDDEN * STEPS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        let mut h: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(HIDDEN), dim::U1, 0f64);
+        for t in 0..STEPS {
+            let xt = x.rows(t*IN, IN).into_owned();
+            h = Self::cell(p, &xt, &h);
+            y.rows_mut(t*HIDDEN, HIDDEN).copy_from(&h);
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let mut steps: Vec<Step> = Vec::with_capacity(STEPS);
+        for t in 0..STEPS {
+            let xt = self.input.rows(t*IN, IN).into_owned();
+            let h = if t > 0 {
+                self.signal.rows((t-1)*HIDDEN, HIDDEN).into_owned()
+            } else {
+                DVector::zeros(HIDDEN)
+            };
+            steps.push(Self::step(p, &xt, &h));
+        }
+
+        (self.chain_element, self.params_derivative) =
+            unroll(&steps, HIDDEN);
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 3] = [1., 0.5, 0.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 0f64].as_slice());
+
+        let y = ElmanLayer::<1, 1, 2>::eval(&p, x);
+        assert_float_eq!(y[0], 1f64.tanh(), abs <= 0.000_000_1);
+        assert_float_eq!(y[1], (0.5*1f64.tanh()).tanh(),
+                         abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 3] = [1., 0.5, 0.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 0f64].as_slice());
+        let mut layer = ElmanLayer::<1, 1, 2>::new();
+
+        let y = layer.forward(&p, x.clone());
+        assert_eq!(y, ElmanLayer::<1, 1, 2>::eval(&p, x));
+        assert_eq!(layer.signal, y);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 10] = [0.5, -0.3, 0.8, 0.1, 0.2,
+                            -0.6, 0.4, 0.7, 0.3, -0.1];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = ElmanLayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (ElmanLayer::<2, 2, 3>::eval(&p, x1) -
+                     ElmanLayer::<2, 2, 3>::eval(&p, x2)) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p: [f64; 10] = [0.5, -0.3, 0.8, 0.1, 0.2,
+                            -0.6, 0.4, 0.7, 0.3, -0.1];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = ElmanLayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..10 {
+            let mut p1 = p;
+            let mut p2 = p;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (ElmanLayer::<2, 2, 3>::eval(&p1, x.clone()) -
+                     ElmanLayer::<2, 2, 3>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Embedding layer for categorical inputs.
+///
+/// Input is a single category index in `0..VOCAB` stored as `f64`,
+/// output is the corresponding learnable row of the embedding table.
+/// Parameters are rows of the table (`VOCAB x DIM`, row-major).
+/// Wrap in `TimeDistributed` to embed several categorical features.
+pub struct EmbeddingLayer<const VOCAB: usize, const DIM: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const VOCAB: usize, const DIM: usize> EmbeddingLayer<VOCAB, DIM>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    fn index(x: &DVector<f64>) -> usize {
+        let idx = x[0].round();
+        assert!(idx >= 0. && idx < VOCAB as f64,
+                "Category index {} out of range!", x[0]);
+
+        idx as usize
+    }
+}
+
+impl<const VOCAB: usize, const DIM: usize> Default
+    for EmbeddingLayer<VOCAB, DIM>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const VOCAB: usize, const DIM: usize> Layer
+    for EmbeddingLayer<VOCAB, DIM>
+{
+    const PARAMS_CNT: usize = VOCAB * DIM;
+    const NEURONS_IN: usize = 1;
+    const NEURONS_OUT: usize = DIM;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let idx = Self::index(&x);
+
+        DVector::from_column_slice(&p[idx*DIM..(idx+1)*DIM])
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    /// Output is piecewise constant in the index,
+    /// so its derivative is zero.
+    fn backward(&mut self, _p: &[f64]) {}
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        let idx = Self::index(x);
+        for i in 0..DIM {
+            matrix[(i, idx*DIM + i)] = 1.;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 6] = [1., 2., 3., 4., 5., 6.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64].as_slice());
+
+        let y = EmbeddingLayer::<3, 2>::eval(&p, x);
+        assert_eq!(y, na::vector![3f64, 4f64]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eval_out_of_range() {
+        let p: [f64; 6] = [1., 2., 3., 4., 5., 6.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+
+        let _ = EmbeddingLayer::<3, 2>::eval(&p, x);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 6] = [1., 2., 3., 4., 5., 6.];
+        let x = DVector::from_column_slice(
+            na::vector![2f64].as_slice());
+        let mut layer = EmbeddingLayer::<3, 2>::new();
+
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![5f64, 6f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 6] = [1., 2., 3., 4., 5., 6.];
+        let x = DVector::from_column_slice(
+            na::vector![2f64].as_slice());
+        let mut layer = EmbeddingLayer::<3, 2>::new();
+
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![0f64;
+                               0f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64].as_slice());
+        let layer = EmbeddingLayer::<3, 2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![0., 0., 1., 0., 0., 0.;
+                               0., 0., 0., 1., 0., 0.]);
+    }
+}
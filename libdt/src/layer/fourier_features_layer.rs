@@ -0,0 +1,225 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::MatrixView;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::gaussian_noise_layer::standard_normal;
+
+/// Fourier feature expansion.
+///
+/// Computes `z = W x` and outputs `sin(z)` followed by `cos(z)`.
+/// Parameters are frequencies `W` (`FREQS x IN`, column-major).
+/// Default initial frequencies are drawn from the standard normal
+/// distribution (random Fourier features). When `LEARNABLE` is
+/// `false` frequencies are frozen and only used by the layer.
+pub struct FourierFeaturesLayer<const IN: usize, const FREQS: usize,
+                                const LEARNABLE: bool = true> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const FREQS: usize, const LEARNABLE: bool>
+    FourierFeaturesLayer<IN, FREQS, LEARNABLE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    fn frequencies(p: &[f64]) ->
+        MatrixView<'_, f64, dim::Dyn, dim::Dyn>
+    {
+        MatrixView::from_slice_generic(p, dim::Dyn(FREQS), dim::Dyn(IN))
+    }
+}
+
+impl<const IN: usize, const FREQS: usize, const LEARNABLE: bool> Default
+    for FourierFeaturesLayer<IN, FREQS, LEARNABLE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const FREQS: usize, const LEARNABLE: bool> Layer
+    for FourierFeaturesLayer<IN, FREQS, LEARNABLE>
+{
+    const PARAMS_CNT: usize = FREQS * IN;
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = 2 * FREQS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let z = Self::frequencies(p) * x;
+
+        DVector::from_fn_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1,
+            |k, _| if k < FREQS { z[k].sin() } else { z[k - FREQS].cos() })
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let w = Self::frequencies(p);
+
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        for k in 0..FREQS {
+            let sin = self.signal[k];
+            let cos = self.signal[FREQS + k];
+            self.chain_element.row_mut(k).copy_from(&(w.row(k) * cos));
+            self.chain_element.row_mut(FREQS + k)
+                .copy_from(&(w.row(k) * -sin));
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        if !LEARNABLE {
+            return matrix;
+        }
+
+        for k in 0..FREQS {
+            let sin = self.signal[k];
+            let cos = self.signal[FREQS + k];
+            for i in 0..IN {
+                matrix[(k, i*FREQS + k)] = cos * x[i];
+                matrix[(FREQS + k, i*FREQS + k)] = -sin * x[i];
+            }
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(standard_normal(&mut rng));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    const P: [f64; 4] = [1., -0.5, 2., 0.3];
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![std::f64::consts::PI / 2.].as_slice());
+
+        let y = FourierFeaturesLayer::<1, 2>::eval(&[1., 2.], x);
+        assert_float_eq!(y[0], 1f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 0f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[2], 0f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[3], -1f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![0.7f64, -1.1f64].as_slice());
+        let mut layer = FourierFeaturesLayer::<2, 2>::new();
+
+        let y = layer.forward(&P, x.clone());
+        assert_eq!(y, FourierFeaturesLayer::<2, 2>::eval(&P, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![0.7f64, -1.1f64].as_slice());
+        let mut layer = FourierFeaturesLayer::<2, 2>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        layer.backward(&P);
+
+        const H: f64 = 0.000_001;
+        for j in 0..2 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (FourierFeaturesLayer::<2, 2>::eval(&P, x1) -
+                     FourierFeaturesLayer::<2, 2>::eval(&P, x2)) / (2.*H);
+            for i in 0..4 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![0.7f64, -1.1f64].as_slice());
+        let mut layer = FourierFeaturesLayer::<2, 2>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..4 {
+            let mut p1 = P;
+            let mut p2 = P;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (FourierFeaturesLayer::<2, 2>::eval(&p1, x.clone()) -
+                     FourierFeaturesLayer::<2, 2>::eval(&p2, x.clone())) /
+                (2.*H);
+            for i in 0..4 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end_frozen() {
+        let x = DVector::from_column_slice(
+            na::vector![0.7f64, -1.1f64].as_slice());
+        let mut layer = FourierFeaturesLayer::<2, 2, false>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        assert_eq!(layer.chain_end(&x), DMatrix::zeros(4, 4));
+    }
+}
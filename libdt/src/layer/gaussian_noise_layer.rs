@@ -0,0 +1,172 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::Layer;
+
+/// Gaussian noise layer.
+///
+/// During training (`forward`) adds independent zero mean
+/// gaussian noise with standard deviation `std` to every neuron.
+/// `eval` is the deterministic expectation, which is identity.
+pub struct GaussianNoiseLayer<const SIZE: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    std: f64,
+    rng: StdRng,
+}
+
+impl<const SIZE: usize> GaussianNoiseLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::identity_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                    dim::Dyn(Self::NEURONS_IN)),
+            std: 0.1f64,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Sets standard deviation of the noise.
+    pub fn set_std(&mut self, std: f64) {
+        assert!(std >= 0.);
+
+        self.std = std;
+    }
+
+    /// Reseeds random number generator, making
+    /// following `forward` calls reproducible.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl<const SIZE: usize> Default for GaussianNoiseLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws sample from standard normal distribution
+/// using Box-Muller transform.
+pub(super) fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1: f64 = 1. - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+
+    (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos()
+}
+
+impl<const SIZE: usize> Layer for GaussianNoiseLayer<SIZE> {
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut x = Self::eval(p, x);
+        for xi in x.iter_mut() {
+            *xi += self.std * standard_normal(&mut self.rng);
+        }
+
+        self.signal = x;
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::identity_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN));
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        
+        let y = GaussianNoiseLayer::<2>::eval(&[], x);
+        assert_eq!(y, na::vector![1f64, 2f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(&[1f64; 1000]);
+        let mut layer = GaussianNoiseLayer::<1000>::new();
+        layer.set_std(0.5);
+        layer.seed(7);
+        
+        let y = layer.forward(&[], x.clone());
+        let noise = &y - &x;
+        assert!(noise.mean().abs() < 0.1);
+        assert!((noise.variance().sqrt() - 0.5).abs() < 0.1);
+
+        layer.seed(7);
+        assert_eq!(layer.forward(&[], x), y);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let mut layer = GaussianNoiseLayer::<2>::new();
+        
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![1f64, 0f64;
+                               0f64, 1f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let layer = GaussianNoiseLayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
@@ -32,6 +32,12 @@ impl<const SIZE: usize> GeLULayer<SIZE>
     }
 }
 
+impl<const SIZE: usize> Default for GeLULayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn gerror(x: f64) -> f64 {
 	0.5*x*(1.0 + ((2.0/std::f64::consts::PI).sqrt()*
 		          (x + 0.044715*x*x*x)).tanh())
@@ -0,0 +1,228 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::MatrixView;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::LinLayer;
+use super::sigma_layer::sigma;
+use super::sigma_layer::sigma_d;
+
+/// Gated linear unit.
+///
+/// Computes `y = (W x + b) * sigma(V x + c)` elementwise.
+/// Parameters are `W, b` followed by `V, c`, both laid out like
+/// parameters of `LinLayer<IN, OUT>`.
+pub struct GLULayer<const IN: usize, const OUT: usize> {
+    linear: Vector::<f64, dim::Dyn,
+                     VecStorage::<f64, dim::Dyn, dim::U1>>,
+    gate: Vector::<f64, dim::Dyn,
+                   VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const OUT: usize> GLULayer<IN, OUT>
+{
+    const HALF_PARAMS_CNT: usize = IN * OUT + OUT;
+
+    pub fn new() -> Self {
+        Self {
+            linear: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            gate: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                               dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    fn halves(p: &[f64]) -> (&[f64], &[f64]) {
+        p.split_at(Self::HALF_PARAMS_CNT)
+    }
+
+    fn linear_and_gate(p: &[f64], x: &DVector<f64>) ->
+        (DVector<f64>, DVector<f64>)
+    {
+        let (pw, pv) = Self::halves(p);
+        let linear = LinLayer::<IN, OUT>::eval(pw, x.clone());
+        let gate = LinLayer::<IN, OUT>::eval(pv, x.clone()).map(sigma);
+
+        (linear, gate)
+    }
+}
+
+impl<const IN: usize, const OUT: usize> Default for GLULayer<IN, OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const OUT: usize> Layer for GLULayer<IN, OUT> {
+    const PARAMS_CNT: usize = 2 * (IN * OUT + OUT);
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (linear, gate) = Self::linear_and_gate(p, &x);
+
+        linear.component_mul(&gate)
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        (self.linear, self.gate) = Self::linear_and_gate(p, &x);
+        self.signal = self.linear.component_mul(&self.gate);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let (pw, pv) = Self::halves(p);
+        let w = MatrixView::from_slice_generic(
+            pw, dim::Dyn(OUT), dim::Dyn(IN));
+        let v = MatrixView::from_slice_generic(
+            pv, dim::Dyn(OUT), dim::Dyn(IN));
+
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        for j in 0..OUT {
+            let s = self.gate[j];
+            let row = w.row(j) * s + v.row(j) * (self.linear[j] * sigma_d(s));
+            self.chain_element.row_mut(j).copy_from(&row);
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        let offset = Self::HALF_PARAMS_CNT;
+        for j in 0..OUT {
+            let s = self.gate[j];
+            let ds = self.linear[j] * sigma_d(s);
+            for i in 0..IN {
+                matrix[(j, i*OUT + j)] = s * x[i];
+                matrix[(j, offset + i*OUT + j)] = ds * x[i];
+            }
+            matrix[(j, IN*OUT + j)] = s;
+            matrix[(j, offset + IN*OUT + j)] = ds;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    const P: [f64; 12] = [1., 3., 2., 4., 0., 1.,
+                          -1., 0.5, 0., 1., 0.2, -0.3];
+
+    #[test]
+    fn test_eval() {
+        // Zero gate input gives gate `1/2`.
+        let p: [f64; 4] = [2., 1., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+
+        let y = GLULayer::<1, 1>::eval(&p, x);
+        assert_float_eq!(y[0], 3.5f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+        let mut layer = GLULayer::<2, 2>::new();
+
+        let y = layer.forward(&P, x.clone());
+        assert_eq!(y, GLULayer::<2, 2>::eval(&P, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+        let mut layer = GLULayer::<2, 2>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        layer.backward(&P);
+
+        const H: f64 = 0.000_001;
+        for j in 0..2 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (GLULayer::<2, 2>::eval(&P, x1) -
+                     GLULayer::<2, 2>::eval(&P, x2)) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+        let mut layer = GLULayer::<2, 2>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..P.len() {
+            let mut p1 = P;
+            let mut p2 = P;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (GLULayer::<2, 2>::eval(&p1, x.clone()) -
+                     GLULayer::<2, 2>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::recurrent::Gate;
+use super::recurrent::Step;
+use super::recurrent::unroll;
+use super::sigma_layer::sigma;
+use super::sigma_layer::sigma_d;
+
+/// Gated recurrent unit layer unrolled over `STEPS` time steps.
+///
+/// Input and output are sequences concatenated into one vector, as
+/// in `ElmanLayer`. For every step, with `h_0 = 0`:
+///
+/// ```text
+/// z_t = sigma(W_z x_t + U_z h_{t-1} + b_z)
+/// r_t = sigma(W_r x_t + U_r h_{t-1} + b_r)
+/// n_t = tanh(W_n x_t + r_t * (U_n h_{t-1}) + b_n)
+/// h_t = (1 - z_t) * n_t + z_t * h_{t-1}
+/// ```
+///
+/// Parameters are three blocks for `z`, `r` and `n` in that order.
+/// Each block is `W` (`HIDDEN x IN`, column-major), `U`
+/// (`HIDDEN x HIDDEN`, column-major) and `b`.
+pub struct GRULayer<const IN: usize,
+                    const HIDDEN: usize,
+                    const STEPS: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> GRULayer<IN, HIDDEN, STEPS>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    /// Returns `z`, `r`, `U_n h` and `n` for one step.
+    fn gates(p: &[f64], x: &DVector<f64>, h: &DVector<f64>) ->
+        (DVector<f64>, DVector<f64>, DVector<f64>, DVector<f64>)
+    {
+        let gz = Gate::nth(p, 0, IN, HIDDEN);
+        let gr = Gate::nth(p, 1, IN, HIDDEN);
+        let gn = Gate::nth(p, 2, IN, HIDDEN);
+
+        let z = (gz.input_part(x) + gz.recurrent_part(h)).map(sigma);
+        let r = (gr.input_part(x) + gr.recurrent_part(h)).map(sigma);
+        let q = gn.recurrent_part(h);
+        let n = (gn.input_part(x) + r.component_mul(&q)).map(f64::tanh);
+
+        (z, r, q, n)
+    }
+
+    fn step(p: &[f64], x: &DVector<f64>, h: &DVector<f64>) -> Step {
+        let gz = Gate::nth(p, 0, IN, HIDDEN);
+        let gr = Gate::nth(p, 1, IN, HIDDEN);
+        let gn = Gate::nth(p, 2, IN, HIDDEN);
+        let (z, r, q, n) = Self::gates(p, x, h);
+
+        // Derivatives of new state over arguments of the gates.
+        let dz = (h - &n).component_mul(&z.map(sigma_d));
+        let dn = z.map(|z| 1. - z).component_mul(&n.map(|n| 1. - n*n));
+        let dr = dn.component_mul(&q)
+            .component_mul(&r.map(sigma_d));
+        let dn_rec = dn.component_mul(&r);
+
+        let input = gz.input_derivative(&dz) +
+            gr.input_derivative(&dr) +
+            gn.input_derivative(&dn);
+        let state = DMatrix::from_diagonal(&z) +
+            gz.recurrent_derivative(&dz) +
+            gr.recurrent_derivative(&dr) +
+            gn.recurrent_derivative(&dn_rec);
+
+        let mut params: DMatrix<f64> =
+            DMatrix::zeros(HIDDEN, Self::PARAMS_CNT);
+        gz.add_params_derivative(&mut params, 0, &dz, &dz, x, h);
+        gr.add_params_derivative(&mut params, 0, &dr, &dr, x, h);
+        gn.add_params_derivative(&mut params, 0, &dn, &dn_rec, x, h);
+
+        Step { input, state, params }
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Default for GRULayer<IN, HIDDEN, STEPS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Layer for GRULayer<IN, HIDDEN, STEPS>
+{
+    const PARAMS_CNT: usize = 3 * Gate::params_cnt(IN, HIDDEN);
+    const NEURONS_IN: usize = IN * STEPS;
+    const NEURONS_OUT: usize = HIDDEN * STEPS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        let mut h: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(HIDDEN), dim::U1, 0f64);
+        for t in 0..STEPS {
+            let xt = x.rows(t*IN, IN).into_owned();
+            let (z, _, _, n) = Self::gates(p, &xt, &h);
+            h = z.map(|z| 1. - z).component_mul(&n) + z.component_mul(&h);
+            y.rows_mut(t*HIDDEN, HIDDEN).copy_from(&h);
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let mut steps: Vec<Step> = Vec::with_capacity(STEPS);
+        for t in 0..STEPS {
+            let xt = self.input.rows(t*IN, IN).into_owned();
+            let h = if t > 0 {
+                self.signal.rows((t-1)*HIDDEN, HIDDEN).into_owned()
+            } else {
+                DVector::zeros(HIDDEN)
+            };
+            steps.push(Self::step(p, &xt, &h));
+        }
+
+        (self.chain_element, self.params_derivative) =
+            unroll(&steps, HIDDEN);
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    fn test_params() -> Vec<f64> {
+        (0..GRULayer::<2, 2, 3>::PARAMS_CNT)
+            .map(|i| ((i*7 % 11) as f64 - 5.) / 5.)
+            .collect()
+    }
+
+    #[test]
+    fn test_eval() {
+        // z = sigma(0) = 0.5, r = sigma(0) = 0.5, n = tanh(x).
+        let p: [f64; 9] = [0., 0., 0., 0., 0., 0., 1., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 0f64].as_slice());
+
+        let y = GRULayer::<1, 1, 2>::eval(&p, x);
+        let h1 = 0.5*1f64.tanh();
+        assert_float_eq!(y[0], h1, abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 0.5*h1, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = GRULayer::<2, 2, 3>::new();
+
+        let y = layer.forward(&p, x.clone());
+        assert_eq!(y, GRULayer::<2, 2, 3>::eval(&p, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = GRULayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (GRULayer::<2, 2, 3>::eval(&p, x1) -
+                     GRULayer::<2, 2, 3>::eval(&p, x2)) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = GRULayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..p.len() {
+            let mut p1 = p.clone();
+            let mut p2 = p.clone();
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (GRULayer::<2, 2, 3>::eval(&p1, x.clone()) -
+                     GRULayer::<2, 2, 3>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// L2 normalization.
+///
+/// Projects signal onto the unit sphere, `y = x / max(|x|, EPS)`.
+/// Every output depends on every input, so `chain_element`
+/// is dense.
+pub struct L2NormalizeLayer<const SIZE: usize> {
+    norm: f64,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+const EPS: f64 = 1e-12;
+
+impl<const SIZE: usize> L2NormalizeLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            norm: 0f64,
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<const SIZE: usize> Default for L2NormalizeLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for L2NormalizeLayer<SIZE> {
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let norm = x.norm().max(EPS);
+
+        x / norm
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.norm = x.norm();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        // Derivative is `(I - y y^T) / |x|`, unless the norm
+        // is clamped to `EPS`.
+        let y = &self.signal;
+        self.chain_element = if self.norm > EPS {
+            (DMatrix::identity(SIZE, SIZE) - y * y.transpose()) / self.norm
+        } else {
+            DMatrix::identity(SIZE, SIZE) / EPS
+        };
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![3f64, -4f64].as_slice());
+
+        let y = L2NormalizeLayer::<2>::eval(&[], x);
+        assert_eq!(y, na::vector![0.6f64, -0.8f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![0f64, 0f64].as_slice());
+        let mut layer = L2NormalizeLayer::<2>::new();
+
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![0f64, 0f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64].as_slice());
+        let mut layer = L2NormalizeLayer::<3>::new();
+
+        let _ = layer.forward(&[], x.clone());
+        layer.backward(&[]);
+
+        const H: f64 = 0.000_001;
+        for j in 0..3 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (L2NormalizeLayer::<3>::eval(&[], x1) -
+                     L2NormalizeLayer::<3>::eval(&[], x2)) / (2.*H);
+            for i in 0..3 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![3f64, -4f64].as_slice());
+        let layer = L2NormalizeLayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
@@ -0,0 +1,198 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Layer normalization.
+///
+/// Normalizes signal to zero mean and unit variance over
+/// its neurons, then applies learnable per neuron gain and bias.
+/// Parameters are laid out as all gains followed by all biases.
+pub struct LayerNormLayer<const SIZE: usize> {
+    normalized: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    std: f64,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+const EPS: f64 = 1e-5;
+
+impl<const SIZE: usize> LayerNormLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            normalized: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                     dim::U1, 0f64),
+            std: 1f64,
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Returns normalized signal and its standard deviation.
+    fn normalize(x: DVector<f64>) -> (DVector<f64>, f64) {
+        let mean = x.mean();
+        let std = (x.variance() + EPS).sqrt();
+
+        (x.add_scalar(-mean) / std, std)
+    }
+}
+
+impl<const SIZE: usize> Default for LayerNormLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for LayerNormLayer<SIZE> {
+    const PARAMS_CNT: usize = 2*SIZE;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (g, b) = p.split_at(SIZE);
+        let (mut x, _) = Self::normalize(x);
+        for ((xi, gi), bi) in x.iter_mut().zip(g).zip(b) {
+            *xi = gi*(*xi) + bi;
+        }
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        (self.normalized, self.std) = Self::normalize(x.clone());
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let n = SIZE as f64;
+        let x = &self.normalized;
+
+        self.chain_element = DMatrix::from_fn_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN),
+            |i, j| {
+                let delta = if i == j { 1. } else { 0. };
+                p[i] / self.std * (delta - 1./n - x[i]*x[j]/n)
+            });
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        let (x, _) = Self::normalize(x.clone());
+        for i in 0..SIZE {
+            matrix[(i, i)] = x[i];
+            matrix[(i, SIZE + i)] = 1.;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut p: Vec<f64> = vec![1f64; SIZE];
+        p.append(&mut vec![0f64; SIZE]);
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 4] = [1., 1., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 3f64].as_slice());
+        
+        let y = LayerNormLayer::<2>::eval(&p, x);
+        assert_eq!(y.len(), 2);
+        assert_float_eq!(y[0], -0.999995f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 0.999995f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 4] = [2., 0.5, 1., -1.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64, 1f64].as_slice());
+        let mut layer = LayerNormLayer::<2>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y.len(), 2);
+        assert_float_eq!(y[0], 2.99999f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[1], -1.4999975f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 6] = [1., 2., 3., 0., 0., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64].as_slice());
+        let mut layer = LayerNormLayer::<3>::new();
+        
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..3 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (LayerNormLayer::<3>::eval(&p, x1) -
+                     LayerNormLayer::<3>::eval(&p, x2)) / (2.*H);
+            for i in 0..3 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 3f64].as_slice());
+        let layer = LayerNormLayer::<2>::new();
+
+        let matrix = layer.chain_end(&x);
+        assert_eq!(matrix.nrows(), 2);
+        assert_eq!(matrix.ncols(), 4);
+        assert_float_eq!(matrix[(0, 0)], -0.999995f64, abs <= 0.000_000_1);
+        assert_float_eq!(matrix[(1, 1)], 0.999995f64, abs <= 0.000_000_1);
+        assert_eq!(matrix.columns(2, 2), na::matrix![1., 0.;
+                                                     0., 1.]);
+    }
+}
@@ -30,6 +30,14 @@ impl<const NEURONS_IN: usize,
     }
 }
 
+impl<const NEURONS_IN: usize, const NEURONS_OUT: usize> Default
+    for LinLayer<NEURONS_IN, NEURONS_OUT>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<const NEURONS_IN: usize, const NEURONS_OUT: usize> Layer
     for LinLayer<NEURONS_IN, NEURONS_OUT>
 {
@@ -0,0 +1,210 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::MatrixView;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Linear layer with low-rank weight matrix.
+///
+/// Computes `y = A B x + b`, where `A` is `OUT x RANK` and `B` is
+/// `RANK x IN`. This needs `RANK * (IN + OUT) + OUT` parameters
+/// instead of `IN * OUT + OUT` of `LinLayer`. Parameters are `A`
+/// and `B` (both column-major) followed by bias `b`.
+pub struct LowRankLinLayer<const IN: usize, const OUT: usize,
+                           const RANK: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const OUT: usize, const RANK: usize>
+    LowRankLinLayer<IN, OUT, RANK>
+{
+    const B_OFFSET: usize = OUT * RANK;
+    const BIAS_OFFSET: usize = OUT * RANK + RANK * IN;
+
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    fn a(p: &[f64]) -> MatrixView<'_, f64, dim::Dyn, dim::Dyn> {
+        MatrixView::from_slice_generic(p, dim::Dyn(OUT), dim::Dyn(RANK))
+    }
+
+    fn b(p: &[f64]) -> MatrixView<'_, f64, dim::Dyn, dim::Dyn> {
+        MatrixView::from_slice_generic(
+            &p[Self::B_OFFSET..], dim::Dyn(RANK), dim::Dyn(IN))
+    }
+
+    fn bias(p: &[f64]) -> MatrixView<'_, f64, dim::Dyn, dim::Dyn> {
+        MatrixView::from_slice_generic(
+            &p[Self::BIAS_OFFSET..], dim::Dyn(OUT), dim::Dyn(1))
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const RANK: usize> Default
+    for LowRankLinLayer<IN, OUT, RANK>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const RANK: usize> Layer
+    for LowRankLinLayer<IN, OUT, RANK>
+{
+    const PARAMS_CNT: usize = RANK * (IN + OUT) + OUT;
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        Self::a(p) * (Self::b(p) * x) + Self::bias(p)
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let a = Self::a(p);
+        let bx = Self::b(p) * &self.input;
+
+        self.chain_element = a * Self::b(p);
+        self.params_derivative = DMatrix::zeros(Self::NEURONS_OUT,
+                                                Self::PARAMS_CNT);
+        for j in 0..OUT {
+            for r in 0..RANK {
+                self.params_derivative[(j, r*OUT + j)] = bx[r];
+                for i in 0..IN {
+                    self.params_derivative[(j, Self::B_OFFSET + i*RANK + r)] =
+                        a[(j, r)] * self.input[i];
+                }
+            }
+            self.params_derivative[(j, Self::BIAS_OFFSET + j)] = 1.;
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    const P: [f64; 8] = [1., 2., 3., 4., 5., 6., 7., 0.5];
+
+    #[test]
+    fn test_eval() {
+        // A = [1; 2], B = [3 4 5], b = [6; 7].
+        let p: [f64; 7] = [1., 2., 3., 4., 5., 6., 7.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 0f64, -1f64].as_slice());
+
+        let y = LowRankLinLayer::<3, 2, 1>::eval(&p, x);
+        assert_eq!(y, na::vector![4f64, 3f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+        let mut layer = LowRankLinLayer::<2, 3, 1>::new();
+
+        let y = layer.forward(&P, x.clone());
+        assert_eq!(y, LowRankLinLayer::<2, 3, 1>::eval(&P, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        // A = [1; 2; 3], B = [4 5].
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+        let mut layer = LowRankLinLayer::<2, 3, 1>::new();
+
+        let _ = layer.forward(&P, x);
+        layer.backward(&P);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![4f64, 5f64;
+                               8f64, 10f64;
+                               12f64, 15f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+        let mut layer = LowRankLinLayer::<2, 3, 1>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        layer.backward(&P);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..P.len() {
+            let mut p1 = P;
+            let mut p2 = P;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (LowRankLinLayer::<2, 3, 1>::eval(&p1, x.clone()) -
+                     LowRankLinLayer::<2, 3, 1>::eval(&p2, x.clone())) /
+                (2.*H);
+            for i in 0..3 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::recurrent::Gate;
+use super::recurrent::Step;
+use super::recurrent::unroll;
+use super::sigma_layer::sigma;
+use super::sigma_layer::sigma_d;
+
+/// Long short-term memory layer unrolled over `STEPS` time steps.
+///
+/// Input and output are sequences concatenated into one vector, as
+/// in `ElmanLayer`. For every step, with `h_0 = c_0 = 0`:
+///
+/// ```text
+/// i_t = sigma(W_i x_t + U_i h_{t-1} + b_i)
+/// f_t = sigma(W_f x_t + U_f h_{t-1} + b_f)
+/// g_t = tanh(W_g x_t + U_g h_{t-1} + b_g)
+/// o_t = sigma(W_o x_t + U_o h_{t-1} + b_o)
+/// c_t = f_t * c_{t-1} + i_t * g_t
+/// h_t = o_t * tanh(c_t)
+/// ```
+///
+/// Output consists of `h_t` only. Parameters are four blocks for
+/// `i`, `f`, `g` and `o` in that order. Each block is `W`
+/// (`HIDDEN x IN`, column-major), `U` (`HIDDEN x HIDDEN`,
+/// column-major) and `b`.
+pub struct LSTMLayer<const IN: usize,
+                     const HIDDEN: usize,
+                     const STEPS: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+/// Values computed by one step of LSTM cell.
+struct Cell {
+    i: DVector<f64>,
+    f: DVector<f64>,
+    g: DVector<f64>,
+    o: DVector<f64>,
+    c: DVector<f64>,
+    h: DVector<f64>,
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> LSTMLayer<IN, HIDDEN, STEPS>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    fn cell(p: &[f64], x: &DVector<f64>,
+            h: &DVector<f64>, c: &DVector<f64>) -> Cell {
+        let gate = |k: usize| {
+            let gate = Gate::nth(p, k, IN, HIDDEN);
+            gate.input_part(x) + gate.recurrent_part(h)
+        };
+
+        let i = gate(0).map(sigma);
+        let f = gate(1).map(sigma);
+        let g = gate(2).map(f64::tanh);
+        let o = gate(3).map(sigma);
+        let c = f.component_mul(c) + i.component_mul(&g);
+        let h = o.component_mul(&c.map(f64::tanh));
+
+        Cell { i, f, g, o, c, h }
+    }
+
+    fn step(p: &[f64], x: &DVector<f64>,
+            h: &DVector<f64>, c: &DVector<f64>) -> (Step, Cell) {
+        let gates: Vec<Gate> = (0..4)
+            .map(|k| Gate::nth(p, k, IN, HIDDEN))
+            .collect();
+        let cell = Self::cell(p, x, h, c);
+
+        // Derivatives of new `c` and new `h` over arguments
+        // of the gates, in order `i`, `f`, `g`, `o`.
+        let tc = cell.c.map(f64::tanh);
+        let k = cell.o.component_mul(&tc.map(|t| 1. - t*t));
+        let dc = [
+            cell.g.component_mul(&cell.i.map(sigma_d)),
+            c.component_mul(&cell.f.map(sigma_d)),
+            cell.i.component_mul(&cell.g.map(|g| 1. - g*g)),
+            DVector::zeros(HIDDEN),
+        ];
+        let dh = [
+            k.component_mul(&dc[0]),
+            k.component_mul(&dc[1]),
+            k.component_mul(&dc[2]),
+            tc.component_mul(&cell.o.map(sigma_d)),
+        ];
+
+        let mut input: DMatrix<f64> = DMatrix::zeros(2*HIDDEN, IN);
+        let mut state: DMatrix<f64> = DMatrix::zeros(2*HIDDEN, 2*HIDDEN);
+        let mut params: DMatrix<f64> =
+            DMatrix::zeros(2*HIDDEN, Self::PARAMS_CNT);
+        for (gate, (dh, dc)) in gates.iter().zip(dh.iter().zip(dc.iter())) {
+            let mut rows = input.rows_mut(0, HIDDEN);
+            rows += gate.input_derivative(dh);
+            let mut rows = input.rows_mut(HIDDEN, HIDDEN);
+            rows += gate.input_derivative(dc);
+
+            let mut block = state.view_mut((0, 0), (HIDDEN, HIDDEN));
+            block += gate.recurrent_derivative(dh);
+            let mut block = state.view_mut((HIDDEN, 0), (HIDDEN, HIDDEN));
+            block += gate.recurrent_derivative(dc);
+
+            gate.add_params_derivative(&mut params, 0, dh, dh, x, h);
+            gate.add_params_derivative(&mut params, HIDDEN, dc, dc, x, h);
+        }
+        state.view_mut((0, HIDDEN), (HIDDEN, HIDDEN)).copy_from(
+            &DMatrix::from_diagonal(&k.component_mul(&cell.f)));
+        state.view_mut((HIDDEN, HIDDEN), (HIDDEN, HIDDEN)).copy_from(
+            &DMatrix::from_diagonal(&cell.f));
+
+        (Step { input, state, params }, cell)
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Default for LSTMLayer<IN, HIDDEN, STEPS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize,
+     const HIDDEN: usize,
+     const STEPS: usize> Layer for LSTMLayer<IN, HIDDEN, STEPS>
+{
+    const PARAMS_CNT: usize = 4 * Gate::params_cnt(IN, HIDDEN);
+    const NEURONS_IN: usize = IN * STEPS;
+    const NEURONS_OUT: usize = HIDDEN * STEPS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        let mut h: DVector<f64> = DVector::zeros(HIDDEN);
+        let mut c: DVector<f64> = DVector::zeros(HIDDEN);
+        for t in 0..STEPS {
+            let xt = x.rows(t*IN, IN).into_owned();
+            let cell = Self::cell(p, &xt, &h, &c);
+            (h, c) = (cell.h, cell.c);
+            y.rows_mut(t*HIDDEN, HIDDEN).copy_from(&h);
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let mut steps: Vec<Step> = Vec::with_capacity(STEPS);
+        let mut h: DVector<f64> = DVector::zeros(HIDDEN);
+        let mut c: DVector<f64> = DVector::zeros(HIDDEN);
+        for t in 0..STEPS {
+            let xt = self.input.rows(t*IN, IN).into_owned();
+            let (step, cell) = Self::step(p, &xt, &h, &c);
+            (h, c) = (cell.h, cell.c);
+            steps.push(step);
+        }
+
+        (self.chain_element, self.params_derivative) =
+            unroll(&steps, HIDDEN);
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    fn test_params() -> Vec<f64> {
+        (0..LSTMLayer::<2, 2, 3>::PARAMS_CNT)
+            .map(|i| ((i*7 % 11) as f64 - 5.) / 5.)
+            .collect()
+    }
+
+    #[test]
+    fn test_eval() {
+        // All gates but `g` are sigma(0) = 0.5, g = tanh(x).
+        let mut p: [f64; 12] = [0.; 12];
+        p[6] = 1.;
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 0f64].as_slice());
+
+        let y = LSTMLayer::<1, 1, 2>::eval(&p, x);
+        let c1 = 0.5*1f64.tanh();
+        assert_float_eq!(y[0], 0.5*c1.tanh(), abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 0.5*(0.5*c1).tanh(), abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = LSTMLayer::<2, 2, 3>::new();
+
+        let y = layer.forward(&p, x.clone());
+        assert_eq!(y, LSTMLayer::<2, 2, 3>::eval(&p, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = LSTMLayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (LSTMLayer::<2, 2, 3>::eval(&p, x1) -
+                     LSTMLayer::<2, 2, 3>::eval(&p, x2)) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = LSTMLayer::<2, 2, 3>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..p.len() {
+            let mut p1 = p.clone();
+            let mut p2 = p.clone();
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (LSTMLayer::<2, 2, 3>::eval(&p1, x.clone()) -
+                     LSTMLayer::<2, 2, 3>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
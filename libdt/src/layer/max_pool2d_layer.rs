@@ -0,0 +1,187 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Two dimensional max pooling layer.
+///
+/// Takes maximum over non-overlapping `POOL` x `POOL` windows
+/// of each of `CH` images of size `HEIGHT` x `WIDTH`. Layout
+/// of the signal is the same as in `Conv2DLayer`. Rows and columns
+/// which do not fill a whole window are dropped.
+///
+/// Maximum is not differentiable where several inputs of one window
+/// are equal; `backward` then passes the whole derivative to the first
+/// of them, which is a valid subgradient.
+pub struct MaxPool2DLayer<const HEIGHT: usize,
+                          const WIDTH: usize,
+                          const CH: usize,
+                          const POOL: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> MaxPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    const OUT_HEIGHT: usize = HEIGHT / POOL;
+    const OUT_WIDTH: usize = WIDTH / POOL;
+
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Index of the first maximal input in window of output `out`.
+    fn argmax(x: &DVector<f64>, out: usize) -> usize {
+        let c = out / (Self::OUT_HEIGHT * Self::OUT_WIDTH);
+        let tr = out / Self::OUT_WIDTH % Self::OUT_HEIGHT;
+        let tc = out % Self::OUT_WIDTH;
+
+        let mut best = (c*HEIGHT + tr*POOL)*WIDTH + tc*POOL;
+        for r in tr*POOL..(tr + 1)*POOL {
+            for col in tc*POOL..(tc + 1)*POOL {
+                let i = (c*HEIGHT + r)*WIDTH + col;
+                if x[i] > x[best] {
+                    best = i;
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> Default
+    for MaxPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const HEIGHT: usize,
+     const WIDTH: usize,
+     const CH: usize,
+     const POOL: usize> Layer
+    for MaxPool2DLayer<HEIGHT, WIDTH, CH, POOL>
+{
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = CH * HEIGHT * WIDTH;
+    const NEURONS_OUT: usize = CH * (HEIGHT / POOL) * (WIDTH / POOL);
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        DVector::from_fn_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1,
+            |out, _| x[Self::argmax(&x, out)])
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for out in 0..Self::NEURONS_OUT {
+            self.chain_element[(out, Self::argmax(&self.input, out))] = 1.;
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 0f64,
+                        4f64, 3f64, 2f64, 1f64].as_slice());
+        
+        let y = MaxPool2DLayer::<2, 4, 1, 2>::eval(&[], x);
+        assert_eq!(y, na::vector![4f64, 3f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64,
+                        -1f64, -2f64, -3f64, -4f64].as_slice());
+        let mut layer = MaxPool2DLayer::<2, 2, 2, 2>::new();
+        
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![4f64, -1f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 0f64,
+                        4f64, 3f64, 3f64, 1f64].as_slice());
+        let mut layer = MaxPool2DLayer::<2, 4, 1, 2>::new();
+        
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![0f64, 0f64, 0f64, 0f64, 1f64, 0f64, 0f64, 0f64;
+                               0f64, 0f64, 1f64, 0f64, 0f64, 0f64, 0f64, 0f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64, 3f64, 4f64].as_slice());
+        let layer = MaxPool2DLayer::<2, 2, 1, 2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
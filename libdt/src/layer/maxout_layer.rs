@@ -0,0 +1,212 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::MatrixView;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::LinLayer;
+
+/// Maxout layer.
+///
+/// Computes `K` affine pieces `z_k = W_k x + b_k` and outputs
+/// their elementwise maximum. Parameters are pieces one after
+/// another, each laid out like parameters of `LinLayer<IN, OUT>`.
+pub struct MaxoutLayer<const IN: usize, const OUT: usize,
+                       const K: usize> {
+    /// Index of the maximal piece for every output.
+    argmax: Vec<usize>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const OUT: usize, const K: usize>
+    MaxoutLayer<IN, OUT, K>
+{
+    const PIECE_PARAMS_CNT: usize = IN * OUT + OUT;
+
+    pub fn new() -> Self {
+        Self {
+            argmax: vec![0usize; OUT],
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    fn piece(p: &[f64], k: usize) -> &[f64] {
+        &p[k*Self::PIECE_PARAMS_CNT..(k+1)*Self::PIECE_PARAMS_CNT]
+    }
+
+    fn pieces(p: &[f64], x: &DVector<f64>) -> (DVector<f64>, Vec<usize>) {
+        let mut y = LinLayer::<IN, OUT>::eval(Self::piece(p, 0), x.clone());
+        let mut argmax = vec![0usize; OUT];
+        for k in 1..K {
+            let z = LinLayer::<IN, OUT>::eval(Self::piece(p, k), x.clone());
+            for j in 0..OUT {
+                if z[j] > y[j] {
+                    y[j] = z[j];
+                    argmax[j] = k;
+                }
+            }
+        }
+
+        (y, argmax)
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const K: usize> Default
+    for MaxoutLayer<IN, OUT, K>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const K: usize> Layer
+    for MaxoutLayer<IN, OUT, K>
+{
+    const PARAMS_CNT: usize = K * (IN * OUT + OUT);
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert!(K > 0);
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        Self::pieces(p, &x).0
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert!(K > 0);
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        (self.signal, self.argmax) = Self::pieces(p, &x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        for j in 0..OUT {
+            let w = MatrixView::from_slice_generic(
+                Self::piece(p, self.argmax[j]),
+                dim::Dyn(OUT), dim::Dyn(IN));
+            self.chain_element.row_mut(j).copy_from(&w.row(j));
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for j in 0..OUT {
+            let offset = self.argmax[j] * Self::PIECE_PARAMS_CNT;
+            for i in 0..IN {
+                matrix[(j, offset + i*OUT + j)] = x[i];
+            }
+            matrix[(j, offset + IN*OUT + j)] = 1.;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    // Pieces `[1 2; 3 4] x + [0; 1]` and `[-1 0; 0 1] x + [2; -3]`.
+    const P: [f64; 12] = [1., 3., 2., 4., 0., 1.,
+                          -1., 0., 0., 1., 2., -3.];
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+
+        let y = MaxoutLayer::<2, 2, 2>::eval(&P, x);
+        assert_eq!(y, na::vector![3f64, 0f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 1f64].as_slice());
+        let mut layer = MaxoutLayer::<2, 2, 2>::new();
+
+        let y = layer.forward(&P, x);
+        assert_eq!(y, na::vector![3f64, 8f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+        let mut layer = MaxoutLayer::<2, 2, 2>::new();
+
+        let _ = layer.forward(&P, x);
+        layer.backward(&P);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![-1f64, 0f64;
+                               3f64, 4f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 0.5f64].as_slice());
+        let mut layer = MaxoutLayer::<2, 2, 2>::new();
+
+        let _ = layer.forward(&P, x.clone());
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..P.len() {
+            let mut p1 = P;
+            let mut p2 = P;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (MaxoutLayer::<2, 2, 2>::eval(&p1, x.clone()) -
+                     MaxoutLayer::<2, 2, 2>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
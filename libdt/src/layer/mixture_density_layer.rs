@@ -0,0 +1,218 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::SoftMaxLayer;
+use super::gaussian_noise_layer::standard_normal;
+
+/// Mixture density network output head.
+///
+/// Turns `3 K` raw outputs of previous layer into parameters of
+/// a mixture of `K` Gaussians over a scalar target: mixture weights
+/// (softmax of first `K` inputs), means (next `K` inputs, unchanged)
+/// and variances (exponent of last `K` inputs). Output has the same
+/// layout. Train it with `MixtureNLL<K>` objective.
+pub struct MixtureDensityLayer<const K: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const K: usize> MixtureDensityLayer<K>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Returns mean of the component with highest peak density.
+    ///
+    /// This is the mode of the mixture when its components
+    /// are well separated.
+    pub fn mode(y: &DVector<f64>) -> f64 {
+        assert_eq!(y.len(), 3 * K);
+
+        let best = (0..K).max_by(|&a, &b| {
+            let peak = |k: usize| y[k] / y[2*K + k].sqrt();
+            peak(a).total_cmp(&peak(b))
+        }).unwrap();
+
+        y[K + best]
+    }
+
+    /// Draws a sample from the mixture.
+    pub fn sample<R: rand::Rng>(y: &DVector<f64>, rng: &mut R) -> f64 {
+        assert_eq!(y.len(), 3 * K);
+
+        let mut u: f64 = rng.gen();
+        let mut k = 0;
+        while k + 1 < K && u >= y[k] {
+            u -= y[k];
+            k += 1;
+        }
+
+        y[K + k] + y[2*K + k].sqrt() * standard_normal(rng)
+    }
+}
+
+impl<const K: usize> Default for MixtureDensityLayer<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize> Layer for MixtureDensityLayer<K> {
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = 3 * K;
+    const NEURONS_OUT: usize = 3 * K;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y = x;
+        let w = SoftMaxLayer::<K>::eval(&[], y.rows(0, K).into_owned());
+        y.rows_mut(0, K).copy_from(&w);
+        for vi in y.rows_mut(2*K, K).iter_mut() {
+            *vi = vi.exp();
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+
+        let w = self.signal.rows(0, K);
+        let softmax = DMatrix::from_diagonal(&w) - w * w.transpose();
+        self.chain_element.view_mut((0, 0), (K, K)).copy_from(&softmax);
+        for k in 0..K {
+            self.chain_element[(K + k, K + k)] = 1.;
+            self.chain_element[(2*K + k, 2*K + k)] = self.signal[2*K + k];
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![0f64, 0f64, -1f64, 2f64, 0f64, 1f64].as_slice());
+
+        let y = MixtureDensityLayer::<2>::eval(&[], x);
+        assert_eq!(y, na::vector![0.5f64, 0.5f64, -1f64, 2f64,
+                                  1f64, 1f64.exp()]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![3f64, 0.5f64, 0f64].as_slice());
+        let mut layer = MixtureDensityLayer::<1>::new();
+
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![1f64, 0.5f64, 1f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![0.3f64, -1f64, -1f64, 2f64, 0.2f64, -0.4f64]
+            .as_slice());
+        let mut layer = MixtureDensityLayer::<2>::new();
+
+        let _ = layer.forward(&[], x.clone());
+        layer.backward(&[]);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (MixtureDensityLayer::<2>::eval(&[], x1) -
+                     MixtureDensityLayer::<2>::eval(&[], x2)) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![0f64, 0f64, 0f64].as_slice());
+        let layer = MixtureDensityLayer::<1>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+
+    #[test]
+    fn test_mode() {
+        let y = DVector::from_column_slice(
+            na::vector![0.3f64, 0.7f64, -1f64, 2f64, 0.01f64, 1f64]
+            .as_slice());
+
+        // Narrow component has higher peak despite smaller weight.
+        assert_eq!(MixtureDensityLayer::<2>::mode(&y), -1f64);
+    }
+
+    #[test]
+    fn test_sample() {
+        let y = DVector::from_column_slice(
+            na::vector![0.25f64, 0.75f64, -10f64, 10f64, 1f64, 1f64]
+            .as_slice());
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        let samples: Vec<f64> = (0..1000)
+            .map(|_| MixtureDensityLayer::<2>::sample(&y, &mut rng))
+            .collect();
+        let low = samples.iter().filter(|s| **s < 0.).count();
+        assert!(low > 200 && low < 300);
+        assert!(samples.iter().all(|s| (s.abs() - 10.).abs() < 6.));
+    }
+}
@@ -14,6 +14,12 @@ pub trait Layer {
     fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64>;
     fn backward(&mut self, p: &[f64]);
     fn chain_element(&self) -> &DMatrix<f64>;
+    /// Derivative of output over parameters for input `x`.
+    ///
+    /// Layers whose derivative over parameters depends on their
+    /// state through the whole input may compute it in `backward`
+    /// and ignore `x`, so `x` must be the input last given to
+    /// `forward`.
     fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>;
     fn default_initial_params() -> Vec<f64>;
 }
@@ -27,8 +33,124 @@ pub use sigma_layer::*;
 mod gelu_layer;
 pub use gelu_layer::*;
 
+mod tanh_layer;
+pub use tanh_layer::*;
+
+mod softplus_layer;
+pub use softplus_layer::*;
+
+mod swish_layer;
+pub use swish_layer::*;
+
+mod gaussian_layer;
+pub use gaussian_layer::*;
+
 mod softmax_layer;
 pub use softmax_layer::*;
 
 mod sum_layer;
 pub use sum_layer::*;
+
+mod relu_layer;
+pub use relu_layer::*;
+
+mod leaky_relu_layer;
+pub use leaky_relu_layer::*;
+
+mod elu_layer;
+pub use elu_layer::*;
+
+mod selu_layer;
+pub use selu_layer::*;
+
+mod prelu_layer;
+pub use prelu_layer::*;
+
+mod param_sigma_layer;
+pub use param_sigma_layer::*;
+
+mod conv1d_layer;
+pub use conv1d_layer::*;
+
+mod conv2d_layer;
+pub use conv2d_layer::*;
+
+mod max_pool2d_layer;
+pub use max_pool2d_layer::*;
+
+mod avg_pool2d_layer;
+pub use avg_pool2d_layer::*;
+
+mod dropout_layer;
+pub use dropout_layer::*;
+
+mod gaussian_noise_layer;
+pub use gaussian_noise_layer::*;
+
+mod layer_norm_layer;
+pub use layer_norm_layer::*;
+
+mod standardize_layer;
+pub use standardize_layer::*;
+
+mod chain;
+pub use chain::*;
+
+mod residual;
+pub use residual::*;
+
+mod parallel;
+pub use parallel::*;
+
+mod time_distributed;
+pub use time_distributed::*;
+
+mod elman_layer;
+pub use elman_layer::*;
+
+mod recurrent;
+
+mod gru_layer;
+pub use gru_layer::*;
+
+mod lstm_layer;
+pub use lstm_layer::*;
+
+mod self_attention_layer;
+pub use self_attention_layer::*;
+
+mod embedding_layer;
+pub use embedding_layer::*;
+
+mod rbf_layer;
+pub use rbf_layer::*;
+
+mod polynomial_features_layer;
+pub use polynomial_features_layer::*;
+
+mod fourier_features_layer;
+pub use fourier_features_layer::*;
+
+mod chebyshev_layer;
+pub use chebyshev_layer::*;
+
+mod low_rank_lin_layer;
+pub use low_rank_lin_layer::*;
+
+mod maxout_layer;
+pub use maxout_layer::*;
+
+mod glu_layer;
+pub use glu_layer::*;
+
+mod scale_shift_layer;
+pub use scale_shift_layer::*;
+
+mod l2_normalize_layer;
+pub use l2_normalize_layer::*;
+
+mod mixture_density_layer;
+pub use mixture_density_layer::*;
+
+mod spline_layer;
+pub use spline_layer::*;
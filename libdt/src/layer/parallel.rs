@@ -0,0 +1,184 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Two layers applied to the same input.
+///
+/// Output of `a` is followed by output of `b`. Parameters
+/// of `a` are followed by parameters of `b`.
+pub struct Parallel<A: Layer, B: Layer> {
+    a: A,
+    b: B,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<A: Layer, B: Layer> Parallel<A, B> {
+    fn concat(ya: DVector<f64>, yb: DVector<f64>) -> DVector<f64> {
+        let mut y = ya.resize_vertically(A::NEURONS_OUT + B::NEURONS_OUT,
+                                         0f64);
+        y.rows_mut(A::NEURONS_OUT, B::NEURONS_OUT).copy_from(&yb);
+
+        y
+    }
+}
+
+impl<A: Layer + Default, B: Layer + Default> Parallel<A, B> {
+    pub fn new() -> Self {
+        const {
+            assert!(A::NEURONS_IN == B::NEURONS_IN,
+                    "Could not construct parallel layers: NEURONS_IN \
+                     of first layer does not match NEURONS_IN of second \
+                     layer!");
+        }
+
+        Self {
+            a: A::default(),
+            b: B::default(),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<A: Layer + Default, B: Layer + Default> Default for Parallel<A, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Layer, B: Layer> Layer for Parallel<A, B> {
+    const PARAMS_CNT: usize = A::PARAMS_CNT + B::PARAMS_CNT;
+    const NEURONS_IN: usize = A::NEURONS_IN;
+    const NEURONS_OUT: usize = A::NEURONS_OUT + B::NEURONS_OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+
+        Self::concat(A::eval_unchecked(pa, x.clone()),
+                     B::eval_unchecked(pb, x))
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+
+        Self::concat(A::eval(pa, x.clone()), B::eval(pb, x))
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+        self.signal = Self::concat(self.a.forward(pa, x.clone()),
+                                   self.b.forward(pb, x));
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let (pa, pb) = p.split_at(A::PARAMS_CNT);
+        self.a.backward(pa);
+        self.b.backward(pb);
+
+        self.chain_element.rows_mut(0, A::NEURONS_OUT)
+            .copy_from(self.a.chain_element());
+        self.chain_element.rows_mut(A::NEURONS_OUT, B::NEURONS_OUT)
+            .copy_from(self.b.chain_element());
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        matrix.view_mut((0, 0), (A::NEURONS_OUT, A::PARAMS_CNT))
+            .copy_from(&self.a.chain_end(x));
+        matrix.view_mut((A::NEURONS_OUT, A::PARAMS_CNT),
+                        (B::NEURONS_OUT, B::PARAMS_CNT))
+            .copy_from(&self.b.chain_end(x));
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut p: Vec<f64> = A::default_initial_params();
+        p.append(&mut B::default_initial_params());
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::LinLayer;
+    use super::super::ReLULayer;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 4] = [2., -1., 1., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+
+        let y = Parallel::<LinLayer<1, 2>, ReLULayer<1>>::eval(&p, x);
+        assert_eq!(y, na::vector![7f64, -3f64, 3f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 4] = [2., -1., 1., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![-3f64].as_slice());
+        let mut layer = Parallel::<LinLayer<1, 2>, ReLULayer<1>>::new();
+
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![-5f64, 3f64, 0f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 4] = [2., -1., 1., 0.];
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let mut layer = Parallel::<LinLayer<1, 2>, ReLULayer<1>>::new();
+
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![2f64;
+                               -1f64;
+                               1f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let layer = Parallel::<LinLayer<1, 1>, LinLayer<1, 1>>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![3f64, 1f64, 0f64, 0f64;
+                               0f64, 0f64, 3f64, 1f64]);
+    }
+}
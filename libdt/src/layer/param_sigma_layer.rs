@@ -0,0 +1,165 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::sigma_layer::sigma;
+use super::sigma_layer::sigma_d;
+
+/// Sigmoid layer with learnable gain and offset per neuron.
+///
+/// Computes `sigma(a_i * x_i + b_i)`. Parameters are laid out
+/// as all gains `a` followed by all offsets `b`.
+pub struct ParamSigmaLayer<const SIZE: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const SIZE: usize> ParamSigmaLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<const SIZE: usize> Default for ParamSigmaLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for ParamSigmaLayer<SIZE> {
+    const PARAMS_CNT: usize = 2*SIZE;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (a, b) = p.split_at(SIZE);
+        let mut x = x;
+        for ((xi, ai), bi) in x.iter_mut().zip(a).zip(b) {
+            *xi = sigma(ai*(*xi) + bi);
+        }
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for (i, a) in p[..SIZE].iter().enumerate() {
+            self.chain_element[(i, i)] = a*sigma_d(self.signal[i]);
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for i in 0..SIZE {
+            let d = sigma_d(self.signal[i]);
+            matrix[(i, i)] = x[i]*d;
+            matrix[(i, SIZE + i)] = d;
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut p: Vec<f64> = vec![1f64; SIZE];
+        p.append(&mut vec![0f64; SIZE]);
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 4] = [1., 2., 0., -1.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        
+        let y = ParamSigmaLayer::<2>::eval(&p, x);
+        assert_eq!(y, na::vector![sigma(1f64), sigma(3f64)]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 4] = [1., 2., 0., -1.];
+        let x = DVector::from_column_slice(
+            na::vector![2f64, -3f64].as_slice());
+        let mut layer = ParamSigmaLayer::<2>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![sigma(2f64), sigma(-7f64)]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 4] = [1., 2., 0., -1.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let mut layer = ParamSigmaLayer::<2>::new();
+        
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![sigma_d(sigma(1f64)), 0f64;
+                               0f64, 2f64*sigma_d(sigma(3f64))]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p: [f64; 4] = [1., 2., 0., -1.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let mut layer = ParamSigmaLayer::<2>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        let d1 = sigma_d(sigma(1f64));
+        let d2 = sigma_d(sigma(3f64));
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![d1, 0f64, d1, 0f64;
+                               0f64, 2f64*d2, 0f64, d2]);
+    }
+}
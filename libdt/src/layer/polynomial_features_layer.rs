@@ -0,0 +1,151 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Polynomial feature expansion.
+///
+/// Every input `x_i` is expanded into powers `x_i, x_i^2, ...,
+/// x_i^DEGREE`, laid out input after input. There are no cross terms.
+pub struct PolynomialFeaturesLayer<const IN: usize, const DEGREE: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const DEGREE: usize> PolynomialFeaturesLayer<IN, DEGREE>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<const IN: usize, const DEGREE: usize> Default
+    for PolynomialFeaturesLayer<IN, DEGREE>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const DEGREE: usize> Layer
+    for PolynomialFeaturesLayer<IN, DEGREE>
+{
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = IN * DEGREE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        DVector::from_fn_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1,
+            |k, _| x[k / DEGREE].powi((k % DEGREE + 1) as i32))
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for i in 0..IN {
+            for d in 1..=DEGREE {
+                self.chain_element[(i*DEGREE + d - 1, i)] =
+                    d as f64 * self.input[i].powi(d as i32 - 1);
+            }
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![2f64, -1f64].as_slice());
+
+        let y = PolynomialFeaturesLayer::<2, 3>::eval(&[], x);
+        assert_eq!(y, na::vector![2f64, 4f64, 8f64, -1f64, 1f64, -1f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![3f64].as_slice());
+        let mut layer = PolynomialFeaturesLayer::<1, 2>::new();
+
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![3f64, 9f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![2f64, -1f64].as_slice());
+        let mut layer = PolynomialFeaturesLayer::<2, 3>::new();
+
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![1f64, 0f64;
+                               4f64, 0f64;
+                               12f64, 0f64;
+                               0f64, 1f64;
+                               0f64, -2f64;
+                               0f64, 3f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![2f64, -1f64].as_slice());
+        let layer = PolynomialFeaturesLayer::<2, 3>::new();
+
+        assert_eq!(layer.chain_end(&x).shape(), (6, 0));
+    }
+}
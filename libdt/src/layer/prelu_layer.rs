@@ -0,0 +1,158 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Parametric ReLU layer with one learnable negative slope per neuron.
+pub struct PReLULayer<const SIZE: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const SIZE: usize> PReLULayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<const SIZE: usize> Default for PReLULayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for PReLULayer<SIZE> {
+    const PARAMS_CNT: usize = SIZE;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut x = x;
+        for (xi, a) in x.iter_mut().zip(p.iter()) {
+            if *xi <= 0. {
+                *xi *= a;
+            }
+        }
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for (i, a) in p.iter().enumerate() {
+            self.chain_element[(i, i)] =
+                if self.input[i] > 0. { 1. } else { *a };
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for i in 0..SIZE {
+            if x[i] <= 0. {
+                matrix[(i, i)] = x[i];
+            }
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        vec![0.25f64; Self::PARAMS_CNT]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 2] = [0.5, 0.25];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+        
+        let y = PReLULayer::<2>::eval(&p, x);
+        assert_eq!(y, na::vector![1f64, -0.5f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 2] = [0.5, 0.25];
+        let x = DVector::from_column_slice(
+            na::vector![-2f64, 3f64].as_slice());
+        let mut layer = PReLULayer::<2>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![-1f64, 3f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 2] = [0.5, 0.25];
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 2f64].as_slice());
+        let mut layer = PReLULayer::<2>::new();
+        
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![0.5f64, 0f64;
+                               0f64, 1f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![-3f64, 2f64].as_slice());
+        let layer = PReLULayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![-3f64, 0f64;
+                               0f64, 0f64]);
+    }
+}
@@ -0,0 +1,213 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Radial basis function layer with Gaussian kernels.
+///
+/// Computes `y_k = exp(-|x - c_k|^2 / (2 s_k^2))`. Parameters are
+/// centres `c_k` (each of size `IN`) followed by widths `s_k`.
+pub struct RBFLayer<const IN: usize, const CENTERS: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const IN: usize, const CENTERS: usize> RBFLayer<IN, CENTERS>
+{
+    const WIDTHS_OFFSET: usize = IN * CENTERS;
+
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    fn center(p: &[f64], k: usize) -> &[f64] {
+        &p[k*IN..(k+1)*IN]
+    }
+}
+
+impl<const IN: usize, const CENTERS: usize> Default
+    for RBFLayer<IN, CENTERS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const CENTERS: usize> Layer
+    for RBFLayer<IN, CENTERS>
+{
+    const PARAMS_CNT: usize = IN * CENTERS + CENTERS;
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = CENTERS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        DVector::from_fn_generic(dim::Dyn(CENTERS), dim::U1, |k, _| {
+            let s = p[Self::WIDTHS_OFFSET + k];
+            let d: f64 = x.iter().zip(Self::center(p, k))
+                .map(|(xj, cj)| (xj - cj)*(xj - cj))
+                .sum();
+
+            (-d / (2.*s*s)).exp()
+        })
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        self.params_derivative = DMatrix::zeros(Self::NEURONS_OUT,
+                                                Self::PARAMS_CNT);
+        for k in 0..CENTERS {
+            let y = self.signal[k];
+            let s = p[Self::WIDTHS_OFFSET + k];
+            let mut d = 0f64;
+            for (j, cj) in Self::center(p, k).iter().enumerate() {
+                let diff = self.input[j] - cj;
+                d += diff*diff;
+
+                self.chain_element[(k, j)] = -y * diff / (s*s);
+                self.params_derivative[(k, k*IN + j)] = y * diff / (s*s);
+            }
+            self.params_derivative[(k, Self::WIDTHS_OFFSET + k)] =
+                y * d / (s*s*s);
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::WIDTHS_OFFSET {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+        p.append(&mut vec![1f64; CENTERS]);
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 6] = [0., 0., 1., 1., 1., 2.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 1f64].as_slice());
+
+        let y = RBFLayer::<2, 2>::eval(&p, x);
+        assert_float_eq!(y[0], (-1f64).exp(), abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 1f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 6] = [0., 0., 1., 1., 1., 2.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 3f64].as_slice());
+        let mut layer = RBFLayer::<2, 2>::new();
+
+        let y = layer.forward(&p, x);
+        assert_float_eq!(y[0], (-5f64).exp(), abs <= 0.000_000_1);
+        assert_float_eq!(y[1], (-0.5f64).exp(), abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 6] = [0.5, -0.3, 0.8, 0.1, 1.2, 0.7];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -0.5f64].as_slice());
+        let mut layer = RBFLayer::<2, 2>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..2 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (RBFLayer::<2, 2>::eval(&p, x1) -
+                     RBFLayer::<2, 2>::eval(&p, x2)) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p: [f64; 6] = [0.5, -0.3, 0.8, 0.1, 1.2, 0.7];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -0.5f64].as_slice());
+        let mut layer = RBFLayer::<2, 2>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut p1 = p;
+            let mut p2 = p;
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (RBFLayer::<2, 2>::eval(&p1, x.clone()) -
+                     RBFLayer::<2, 2>::eval(&p2, x.clone())) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::MatrixView;
+use na::base::dimension as dim;
+
+/// Gate of a recurrent cell computing `W x + U h + b`.
+///
+/// Gate parameters are laid out as `W` (`hidden x input`,
+/// column-major), `U` (`hidden x hidden`, column-major) and `b`.
+pub(super) struct Gate<'a> {
+    p: &'a [f64],
+    k: usize,
+    input: usize,
+    hidden: usize,
+}
+
+impl<'a> Gate<'a> {
+    pub(super) const fn params_cnt(input: usize, hidden: usize) -> usize {
+        hidden * input + hidden * hidden + hidden
+    }
+
+    /// `k`-th gate of a cell whose parameters are `p`.
+    pub(super) fn nth(p: &'a [f64], k: usize,
+                      input: usize, hidden: usize) -> Self {
+        let size = Self::params_cnt(input, hidden);
+
+        Self {
+            p: &p[k*size..(k+1)*size],
+            k,
+            input,
+            hidden,
+        }
+    }
+
+    fn w(&self) -> MatrixView<'a, f64, dim::Dyn, dim::Dyn> {
+        MatrixView::from_slice_generic(
+            self.p, dim::Dyn(self.hidden), dim::Dyn(self.input))
+    }
+
+    fn u(&self) -> MatrixView<'a, f64, dim::Dyn, dim::Dyn> {
+        MatrixView::from_slice_generic(
+            &self.p[self.hidden * self.input..],
+            dim::Dyn(self.hidden), dim::Dyn(self.hidden))
+    }
+
+    fn b(&self) -> MatrixView<'a, f64, dim::Dyn, dim::U1> {
+        MatrixView::from_slice_generic(
+            &self.p[self.hidden * self.input + self.hidden * self.hidden..],
+            dim::Dyn(self.hidden), dim::U1)
+    }
+
+    /// Input part `W x + b` of the gate.
+    pub(super) fn input_part(&self, x: &DVector<f64>) -> DVector<f64> {
+        self.w() * x + self.b()
+    }
+
+    /// Recurrent part `U h` of the gate.
+    pub(super) fn recurrent_part(&self, h: &DVector<f64>) -> DVector<f64> {
+        self.u() * h
+    }
+
+    /// `diag(d) W`, derivative over input of quantity whose
+    /// derivative over the gate's input part is `diag(d)`.
+    pub(super) fn input_derivative(&self, d: &DVector<f64>) -> DMatrix<f64> {
+        DMatrix::from_diagonal(d) * self.w()
+    }
+
+    /// `diag(d) U`, as `input_derivative` but for recurrent part.
+    pub(super) fn recurrent_derivative(&self, d: &DVector<f64>)
+        -> DMatrix<f64>
+    {
+        DMatrix::from_diagonal(d) * self.u()
+    }
+
+    /// Adds derivatives over parameters of the gate to rows of `matrix`
+    /// starting at `row`. `d_in` and `d_rec` are (diagonal) derivatives
+    /// over input and recurrent part of the gate.
+    pub(super) fn add_params_derivative(&self, matrix: &mut DMatrix<f64>,
+                                        row: usize,
+                                        d_in: &DVector<f64>,
+                                        d_rec: &DVector<f64>,
+                                        x: &DVector<f64>,
+                                        h: &DVector<f64>) {
+        let (input, hidden) = (self.input, self.hidden);
+        let offset = self.k * Self::params_cnt(input, hidden);
+        let u_offset = offset + hidden * input;
+        let b_offset = u_offset + hidden * hidden;
+
+        for i in 0..hidden {
+            for j in 0..input {
+                matrix[(row + i, offset + j*hidden + i)] += d_in[i] * x[j];
+            }
+            for j in 0..hidden {
+                matrix[(row + i, u_offset + j*hidden + i)] +=
+                    d_rec[i] * h[j];
+            }
+            matrix[(row + i, b_offset + i)] += d_in[i];
+        }
+    }
+}
+
+/// Local derivatives of one step of a recurrent cell.
+pub(super) struct Step {
+    /// Derivative of new state over input of the step.
+    pub(super) input: DMatrix<f64>,
+    /// Derivative of new state over previous state.
+    pub(super) state: DMatrix<f64>,
+    /// Derivative of new state over parameters, with
+    /// previous state held constant.
+    pub(super) params: DMatrix<f64>,
+}
+
+/// Accumulates local derivatives of the cell through the unrolled
+/// sequence (backpropagation through time). Output of every step is
+/// the first `out` entries of its state.
+///
+/// Returns derivatives of concatenated outputs over concatenated
+/// inputs and over parameters.
+pub(super) fn unroll(steps: &[Step], out: usize) ->
+    (DMatrix<f64>, DMatrix<f64>)
+{
+    let state_size = steps[0].state.nrows();
+    let input = steps[0].input.ncols();
+    let params_cnt = steps[0].params.ncols();
+
+    let mut chain_element: DMatrix<f64> =
+        DMatrix::zeros(out * steps.len(), input * steps.len());
+    let mut chain_end: DMatrix<f64> =
+        DMatrix::zeros(out * steps.len(), params_cnt);
+
+    let mut dx: DMatrix<f64> =
+        DMatrix::zeros(state_size, input * steps.len());
+    let mut dp: DMatrix<f64> =
+        DMatrix::zeros(state_size, params_cnt);
+    for (t, step) in steps.iter().enumerate() {
+        dx = &step.state * dx;
+        dx.columns_mut(t*input, input).copy_from(&step.input);
+        dp = &step.state * dp + &step.params;
+
+        chain_element.rows_mut(t*out, out).copy_from(&dx.rows(0, out));
+        chain_end.rows_mut(t*out, out).copy_from(&dp.rows(0, out));
+    }
+
+    (chain_element, chain_end)
+}
@@ -0,0 +1,146 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Residual (skip) connection around a layer.
+///
+/// Computes `x + inner(x)`, so `L` must have as many outputs
+/// as inputs. Use `Chain` to wrap more than one layer.
+pub struct Residual<L: Layer> {
+    inner: L,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<L: Layer + Default> Residual<L> {
+    pub fn new() -> Self {
+        const {
+            assert!(L::NEURONS_IN == L::NEURONS_OUT,
+                    "Could not construct residual connection: \
+                     NEURONS_IN of layer does not match its NEURONS_OUT!");
+        }
+
+        Self {
+            inner: L::default(),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<L: Layer + Default> Default for Residual<L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Layer> Layer for Residual<L> {
+    const PARAMS_CNT: usize = L::PARAMS_CNT;
+    const NEURONS_IN: usize = L::NEURONS_IN;
+    const NEURONS_OUT: usize = L::NEURONS_OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        L::eval_unchecked(p, x.clone()) + x
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        L::eval(p, x.clone()) + x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = self.inner.forward(p, x.clone()) + x;
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.inner.backward(p);
+
+        self.chain_element = self.inner.chain_element().clone();
+        for i in 0..Self::NEURONS_OUT {
+            self.chain_element[(i, i)] += 1.;
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.inner.chain_end(x)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        L::default_initial_params()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::LinLayer;
+    use super::super::ReLULayer;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+
+        let y = Residual::<ReLULayer<2>>::eval(&[], x);
+        assert_eq!(y, na::vector![2f64, -2f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 6] = [1., 1., 1., 0., 7., 7.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let mut layer = Residual::<LinLayer<2, 2>>::new();
+
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![11., 10.]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 6] = [1., 1., 1., 0., 7., 7.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let mut layer = Residual::<LinLayer<2, 2>>::new();
+
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![2., 1.;
+                               1., 1.]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let layer = Residual::<LinLayer<2, 2>>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![1., 0., 2., 0., 1., 0.;
+                               0., 1., 0., 2., 0., 1.]);
+    }
+}
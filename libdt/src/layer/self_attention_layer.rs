@@ -0,0 +1,289 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::MatrixView;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+use super::SoftMaxLayer;
+
+/// Single-head scaled dot-product self-attention.
+///
+/// Input is a sequence of `SEQ` tokens of size `DIM` concatenated
+/// into one vector. For every token `q_t = W_q x_t`, `k_t = W_k x_t`
+/// and `v_t = W_v x_t`, and output token is
+/// `y_t = sum_s a_ts v_s`, where `a_t = softmax(q_t^T k_s / sqrt(DIM))`.
+///
+/// Parameters are `W_q`, `W_k` and `W_v` (each `DIM x DIM`,
+/// column-major) in that order.
+pub struct SelfAttentionLayer<const SEQ: usize, const DIM: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+    params_derivative: Matrix::<f64, dim::Dyn, dim::Dyn,
+                                VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+/// Intermediate values of attention, tokens are columns.
+struct Attention {
+    q: DMatrix<f64>,
+    k: DMatrix<f64>,
+    v: DMatrix<f64>,
+    /// Row `t` holds weights `a_t`.
+    a: DMatrix<f64>,
+}
+
+impl<const SEQ: usize, const DIM: usize> SelfAttentionLayer<SEQ, DIM>
+{
+    const WEIGHTS_CNT: usize = DIM * DIM;
+
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+            params_derivative: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                            dim::Dyn(Self::PARAMS_CNT), 0f64),
+        }
+    }
+
+    fn weights(p: &[f64], k: usize) ->
+        MatrixView<'_, f64, dim::Dyn, dim::Dyn>
+    {
+        MatrixView::from_slice_generic(
+            &p[k*Self::WEIGHTS_CNT..], dim::Dyn(DIM), dim::Dyn(DIM))
+    }
+
+    fn tokens(x: &DVector<f64>) ->
+        MatrixView<'_, f64, dim::Dyn, dim::Dyn>
+    {
+        MatrixView::from_slice_generic(
+            x.as_slice(), dim::Dyn(DIM), dim::Dyn(SEQ))
+    }
+
+    fn attention(p: &[f64], x: &DVector<f64>) -> Attention {
+        let x = Self::tokens(x);
+        let q = Self::weights(p, 0) * x;
+        let k = Self::weights(p, 1) * x;
+        let v = Self::weights(p, 2) * x;
+
+        let scores = q.transpose() * &k / (DIM as f64).sqrt();
+        let mut a: DMatrix<f64> = DMatrix::zeros(SEQ, SEQ);
+        for t in 0..SEQ {
+            let a_t = SoftMaxLayer::<SEQ>::eval(
+                &[], scores.row(t).transpose());
+            a.set_row(t, &a_t.transpose());
+        }
+
+        Attention { q, k, v, a }
+    }
+}
+
+impl<const SEQ: usize, const DIM: usize> Default
+    for SelfAttentionLayer<SEQ, DIM>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SEQ: usize, const DIM: usize> Layer
+    for SelfAttentionLayer<SEQ, DIM>
+{
+    const PARAMS_CNT: usize = 3 * DIM * DIM;
+    const NEURONS_IN: usize = SEQ * DIM;
+    const NEURONS_OUT: usize = SEQ * DIM;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let att = Self::attention(p, &x);
+        let y = att.v * att.a.transpose();
+
+        DVector::from_column_slice(y.as_slice())
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        let att = Self::attention(p, &self.input);
+        let x = Self::tokens(&self.input);
+        let wk = Self::weights(p, 1);
+        let wv = Self::weights(p, 2);
+        let scale = 1. / (DIM as f64).sqrt();
+
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        self.params_derivative = DMatrix::zeros(Self::NEURONS_OUT,
+                                                Self::PARAMS_CNT);
+        for t in 0..SEQ {
+            // `g` is derivative of `y_t` over scores of token `t`,
+            // through softmax Jacobian `diag(a_t) - a_t a_t^T`.
+            let a_t = att.a.row(t).transpose();
+            let jm = DMatrix::from_diagonal(&a_t) - &a_t * a_t.transpose();
+            let g = &att.v * jm;
+            let gk = &g * att.k.transpose() * scale;
+            let gx = &g * x.transpose() * scale;
+            let q_t = att.q.column(t);
+            let x_t = x.column(t);
+
+            for u in 0..SEQ {
+                let mut block = g.column(u) * q_t.transpose() * wk * scale +
+                    wv * a_t[u];
+                if u == t {
+                    block += &gk * Self::weights(p, 0);
+                }
+                self.chain_element.view_mut((t*DIM, u*DIM), (DIM, DIM))
+                    .copy_from(&block);
+            }
+
+            let xa = x * &a_t;
+            for i in 0..DIM {
+                for j in 0..DIM {
+                    let col = j*DIM + i;
+                    let mut rows = self.params_derivative
+                        .view_mut((t*DIM, 0), (DIM, Self::PARAMS_CNT));
+
+                    rows.column_mut(col).copy_from(&(gk.column(i) * x_t[j]));
+                    rows.column_mut(Self::WEIGHTS_CNT + col)
+                        .copy_from(&(gx.column(j) * q_t[i]));
+                    rows[(i, 2*Self::WEIGHTS_CNT + col)] = xa[j];
+                }
+            }
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        self.params_derivative.clone()
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    fn test_params() -> Vec<f64> {
+        (0..SelfAttentionLayer::<3, 2>::PARAMS_CNT)
+            .map(|i| ((i*7 % 11) as f64 - 5.) / 5.)
+            .collect()
+    }
+
+    #[test]
+    fn test_eval() {
+        // Identical keys give uniform attention,
+        // so every output token is mean of values.
+        let p: [f64; 3] = [1., 0., 2.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 3f64].as_slice());
+
+        let y = SelfAttentionLayer::<2, 1>::eval(&p, x);
+        assert_float_eq!(y[0], 4f64, abs <= 0.000_000_1);
+        assert_float_eq!(y[1], 4f64, abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = SelfAttentionLayer::<3, 2>::new();
+
+        let y = layer.forward(&p, x.clone());
+        assert_eq!(y, SelfAttentionLayer::<3, 2>::eval(&p, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = SelfAttentionLayer::<3, 2>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..6 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (SelfAttentionLayer::<3, 2>::eval(&p, x1) -
+                     SelfAttentionLayer::<3, 2>::eval(&p, x2)) / (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64, 0.5f64,
+                        0.3f64, -0.7f64, 1.2f64].as_slice());
+        let mut layer = SelfAttentionLayer::<3, 2>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..p.len() {
+            let mut p1 = p.clone();
+            let mut p2 = p.clone();
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (SelfAttentionLayer::<3, 2>::eval(&p1, x.clone()) -
+                     SelfAttentionLayer::<3, 2>::eval(&p2, x.clone())) /
+                (2.*H);
+            for i in 0..6 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
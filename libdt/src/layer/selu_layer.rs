@@ -0,0 +1,156 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+pub struct SELULayer<const SIZE: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const SIZE: usize> SELULayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<const SIZE: usize> Default for SELULayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const SELU_LAMBDA: f64 = 1.050_700_987_355_480_5;
+const SELU_ALPHA: f64 = 1.673_263_242_354_377_3;
+
+fn selu(x: f64) -> f64 {
+    if x > 0. {
+        SELU_LAMBDA*x
+    } else {
+        SELU_LAMBDA*SELU_ALPHA*x.exp_m1()
+    }
+}
+
+fn selu_d(s: f64) -> f64 {
+    if s > 0. { SELU_LAMBDA } else { s + SELU_LAMBDA*SELU_ALPHA }
+}
+
+impl<const SIZE: usize> Layer for SELULayer<SIZE> {
+    const PARAMS_CNT: usize = 0;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(_p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut x = x;
+        for xi in x.iter_mut() {
+            *xi = selu(*xi);
+        }
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, _p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for i in 0..SIZE {
+            self.chain_element[(i, i)] = selu_d(self.signal[i]);
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -2f64].as_slice());
+        
+        let y = SELULayer::<2>::eval(&[], x);
+        assert_eq!(y.len(), 2);
+        assert_float_eq!(y[0], 1.0507009874f64, abs <= 0.000_000_000_1);
+        assert_float_eq!(y[1], -1.5201664686f64, abs <= 0.000_000_000_1);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![2f64, -3f64].as_slice());
+        let mut layer = SELULayer::<2>::new();
+        
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![selu(2f64), selu(-3f64)]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, 2f64].as_slice());
+        let mut layer = SELULayer::<2>::new();
+        
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        let chain_element = layer.chain_element;
+        assert_eq!(chain_element.nrows(), 2);
+        assert_eq!(chain_element.ncols(), 2);
+        assert_float_eq!(chain_element[(0, 0)], 0.6467686030f64, abs <= 0.000_000_000_1);
+        assert_float_eq!(chain_element[(0, 1)], 0f64, abs <= 0.000_000_000_1);
+        assert_float_eq!(chain_element[(1, 0)], 0f64, abs <= 0.000_000_000_1);
+        assert_float_eq!(chain_element[(1, 1)], SELU_LAMBDA, abs <= 0.000_000_000_1);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let layer = SELULayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x).ncols(), 0);
+    }
+}
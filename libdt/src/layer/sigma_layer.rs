@@ -28,11 +28,19 @@ impl<const SIZE: usize> SigmaLayer<SIZE>
     }
 }
 
-fn sigma(x: f64) -> f64 {
+impl<const SIZE: usize> Default for SigmaLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Logistic sigmoid.
+pub(super) fn sigma(x: f64) -> f64 {
     ((x / 2.).tanh() + 1.) / 2.
 }
 
-fn sigma_d(s: f64) -> f64 {
+/// Derivative of `sigma` expressed by its value.
+pub(super) fn sigma_d(s: f64) -> f64 {
     s*(1. - s)
 }
 
@@ -29,6 +29,12 @@ impl<const SIZE: usize> SoftMaxLayer<SIZE>
     }
 }
 
+impl<const SIZE: usize> Default for SoftMaxLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn softmax_d(signal: &DVector<f64>, i: usize, j: usize) -> f64 {
     (if i == j { signal[i] } else { 0f64 }) -
     signal[i] * signal[j]
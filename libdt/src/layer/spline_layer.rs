@@ -0,0 +1,276 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Kolmogorov-Arnold layer with learnable spline on every edge.
+///
+/// Computes `y_j = sum_i f_ji(x_i)`, where every `f_ji` is a cubic
+/// B-spline on uniform grid of `KNOTS` points spanning `[-1, 1]`,
+/// so inputs should be scaled there first (e.g. by `TanhLayer`).
+/// Every spline has `KNOTS + 2` coefficients. Parameters are
+/// coefficients of `f_j0, f_j1, ...` for every output `j` in turn.
+pub struct SplineLayer<const IN: usize, const OUT: usize,
+                       const KNOTS: usize> {
+    input: Vector::<f64, dim::Dyn,
+                    VecStorage::<f64, dim::Dyn, dim::U1>>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+/// Cardinal cubic B-spline supported on `[0, 4]`.
+fn cubic(u: f64) -> f64 {
+    match u {
+        u if (0f64..1f64).contains(&u) => u*u*u / 6.,
+        u if (1f64..2f64).contains(&u) =>
+            (-3.*u*u*u + 12.*u*u - 12.*u + 4.) / 6.,
+        u if (2f64..3f64).contains(&u) =>
+            (3.*u*u*u - 24.*u*u + 60.*u - 44.) / 6.,
+        u if (3f64..4f64).contains(&u) => (4. - u)*(4. - u)*(4. - u) / 6.,
+        _ => 0.,
+    }
+}
+
+fn cubic_d(u: f64) -> f64 {
+    match u {
+        u if (0f64..1f64).contains(&u) => u*u / 2.,
+        u if (1f64..2f64).contains(&u) => (-3.*u*u + 8.*u - 4.) / 2.,
+        u if (2f64..3f64).contains(&u) => (3.*u*u - 16.*u + 20.) / 2.,
+        u if (3f64..4f64).contains(&u) => -(4. - u)*(4. - u) / 2.,
+        _ => 0.,
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const KNOTS: usize>
+    SplineLayer<IN, OUT, KNOTS>
+{
+    const BASIS_CNT: usize = KNOTS + 2;
+    const STEP: f64 = 2. / (KNOTS as f64 - 1.);
+
+    pub fn new() -> Self {
+        Self {
+            input: Vector::from_element_generic(dim::Dyn(Self::NEURONS_IN),
+                                                 dim::U1, 0f64),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Position of `x` relative to support of `b`-th basis function,
+    /// in grid steps.
+    fn grid_position(x: f64, b: usize) -> f64 {
+        (x + 1.) / Self::STEP - b as f64 + 3.
+    }
+
+    fn basis(x: f64) -> Vec<f64> {
+        (0..Self::BASIS_CNT)
+            .map(|b| cubic(Self::grid_position(x, b)))
+            .collect()
+    }
+
+    fn basis_derivative(x: f64) -> Vec<f64> {
+        (0..Self::BASIS_CNT)
+            .map(|b| cubic_d(Self::grid_position(x, b)) / Self::STEP)
+            .collect()
+    }
+
+    fn coefficients(p: &[f64], j: usize, i: usize) -> &[f64] {
+        let offset = (j*IN + i) * Self::BASIS_CNT;
+
+        &p[offset..offset + Self::BASIS_CNT]
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const KNOTS: usize> Default
+    for SplineLayer<IN, OUT, KNOTS>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const IN: usize, const OUT: usize, const KNOTS: usize> Layer
+    for SplineLayer<IN, OUT, KNOTS>
+{
+    const PARAMS_CNT: usize = OUT * IN * (KNOTS + 2);
+    const NEURONS_IN: usize = IN;
+    const NEURONS_OUT: usize = OUT;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert!(KNOTS >= 2);
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let basis: Vec<Vec<f64>> = x.iter()
+            .map(|xi| Self::basis(*xi))
+            .collect();
+
+        DVector::from_fn_generic(dim::Dyn(OUT), dim::U1, |j, _| {
+            (0..IN).map(|i| {
+                Self::coefficients(p, j, i).iter().zip(&basis[i])
+                    .map(|(c, b)| c*b)
+                    .sum::<f64>()
+            }).sum()
+        })
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.input = x.clone();
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::zeros(Self::NEURONS_OUT,
+                                            Self::NEURONS_IN);
+        for i in 0..IN {
+            let basis_d = Self::basis_derivative(self.input[i]);
+            for j in 0..OUT {
+                self.chain_element[(j, i)] =
+                    Self::coefficients(p, j, i).iter().zip(&basis_d)
+                    .map(|(c, b)| c*b)
+                    .sum();
+            }
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for i in 0..IN {
+            let basis = Self::basis(x[i]);
+            for j in 0..OUT {
+                let offset = (j*IN + i) * Self::BASIS_CNT;
+                for (b, bb) in basis.iter().enumerate() {
+                    matrix[(j, offset + b)] = *bb;
+                }
+            }
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        let mut p: Vec<f64> =
+            Vec::with_capacity(Self::PARAMS_CNT);
+        for _ in 0..Self::PARAMS_CNT {
+            p.push(rng.gen_range(-1.0..1.0));
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    fn test_params() -> Vec<f64> {
+        (0..SplineLayer::<2, 2, 4>::PARAMS_CNT)
+            .map(|i| ((i*7 % 11) as f64 - 5.) / 5.)
+            .collect()
+    }
+
+    #[test]
+    fn test_eval() {
+        // B-splines sum up to one, so equal coefficients
+        // give constant function.
+        let p = [2f64; 6];
+        let x_values = [-1f64, -0.3f64, 0.5f64, 1f64];
+
+        for x in x_values {
+            let x = DVector::from_column_slice(&[x]);
+            let y = SplineLayer::<1, 1, 4>::eval(&p, x);
+            assert_float_eq!(y[0], 2f64, abs <= 0.000_000_1);
+        }
+    }
+
+    #[test]
+    fn test_forward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![0.4f64, -0.7f64].as_slice());
+        let mut layer = SplineLayer::<2, 2, 4>::new();
+
+        let y = layer.forward(&p, x.clone());
+        assert_eq!(y, SplineLayer::<2, 2, 4>::eval(&p, x));
+    }
+
+    #[test]
+    fn test_backward() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![0.4f64, -0.7f64].as_slice());
+        let mut layer = SplineLayer::<2, 2, 4>::new();
+
+        let _ = layer.forward(&p, x.clone());
+        layer.backward(&p);
+
+        const H: f64 = 0.000_001;
+        for j in 0..2 {
+            let mut x1 = x.clone();
+            let mut x2 = x.clone();
+            x1[j] += H;
+            x2[j] -= H;
+            let d = (SplineLayer::<2, 2, 4>::eval(&p, x1) -
+                     SplineLayer::<2, 2, 4>::eval(&p, x2)) / (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(layer.chain_element()[(i, j)], d[i],
+                                 abs <= 0.000_001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let p = test_params();
+        let x = DVector::from_column_slice(
+            na::vector![0.4f64, -0.7f64].as_slice());
+        let layer = SplineLayer::<2, 2, 4>::new();
+
+        let matrix = layer.chain_end(&x);
+
+        const H: f64 = 0.000_001;
+        for j in 0..p.len() {
+            let mut p1 = p.clone();
+            let mut p2 = p.clone();
+            p1[j] += H;
+            p2[j] -= H;
+            let d = (SplineLayer::<2, 2, 4>::eval(&p1, x.clone()) -
+                     SplineLayer::<2, 2, 4>::eval(&p2, x.clone())) /
+                (2.*H);
+            for i in 0..2 {
+                assert_float_eq!(matrix[(i, j)], d[i], abs <= 0.000_001);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Input standardization layer.
+///
+/// Computes `(x_i - mean_i) / std_i`. Means followed by standard
+/// deviations are stored in parameter vector, so that they travel
+/// with the network, but they are frozen: `chain_end` is zero, so
+/// trainers never change them. Use `params_from_data` to compute them.
+pub struct StandardizeLayer<const SIZE: usize> {
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<const SIZE: usize> StandardizeLayer<SIZE>
+{
+    pub fn new() -> Self {
+        Self {
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+
+    /// Computes layer parameters standardizing given dataset.
+    ///
+    /// Neurons which are constant over the dataset get
+    /// standard deviation of one.
+    pub fn params_from_data(x_values: &[DVector<f64>]) -> Vec<f64> {
+        assert!(!x_values.is_empty());
+
+        let n = x_values.len() as f64;
+        let mut mean: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(SIZE), dim::U1, 0f64);
+        for x in x_values {
+            assert_eq!(x.len(), SIZE);
+            mean += x;
+        }
+        mean /= n;
+
+        let mut var: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(SIZE), dim::U1, 0f64);
+        for x in x_values {
+            var += (x - &mean).map(|d| d*d);
+        }
+        var /= n;
+
+        let mut p: Vec<f64> = mean.as_slice().to_vec();
+        p.extend(var.iter().map(|v| if *v > 0. { v.sqrt() } else { 1. }));
+
+        p
+    }
+}
+
+impl<const SIZE: usize> Default for StandardizeLayer<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> Layer for StandardizeLayer<SIZE> {
+    const PARAMS_CNT: usize = 2*SIZE;
+    const NEURONS_IN: usize = SIZE;
+    const NEURONS_OUT: usize = SIZE;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        Self::eval(p, x)
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let (mean, std) = p.split_at(SIZE);
+        let mut x = x;
+        for ((xi, m), s) in x.iter_mut().zip(mean).zip(std) {
+            *xi = (*xi - m) / s;
+        }
+
+        x
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        self.signal = Self::eval(p, x);
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        self.chain_element = DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::Dyn(Self::NEURONS_IN), 0f64);
+        for (i, s) in p[SIZE..].iter().enumerate() {
+            self.chain_element[(i, i)] = 1. / s;
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, _x: &DVector<f64>) -> DMatrix<f64>
+    {
+        DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64)
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        let mut p: Vec<f64> = vec![0f64; SIZE];
+        p.append(&mut vec![1f64; SIZE]);
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 4] = [1., -2., 2., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![3f64, -1f64].as_slice());
+        
+        let y = StandardizeLayer::<2>::eval(&p, x);
+        assert_eq!(y, na::vector![1f64, 2f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let p: [f64; 4] = [1., -2., 2., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![-1f64, -2f64].as_slice());
+        let mut layer = StandardizeLayer::<2>::new();
+        
+        let y = layer.forward(&p, x);
+        assert_eq!(y, na::vector![-1f64, 0f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let p: [f64; 4] = [1., -2., 2., 0.5];
+        let x = DVector::from_column_slice(
+            na::vector![3f64, -1f64].as_slice());
+        let mut layer = StandardizeLayer::<2>::new();
+        
+        let _ = layer.forward(&p, x);
+        layer.backward(&p);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![0.5f64, 0f64;
+                               0f64, 2f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, 2f64].as_slice());
+        let layer = StandardizeLayer::<2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   DMatrix::from_element(2, 4, 0f64));
+    }
+
+    #[test]
+    fn test_params_from_data() {
+        let x_values: Vec<DVector<f64>> = vec![
+            DVector::from_column_slice(na::vector![1f64, 5f64].as_slice()),
+            DVector::from_column_slice(na::vector![3f64, 5f64].as_slice())];
+
+        let p = StandardizeLayer::<2>::params_from_data(&x_values);
+        assert_eq!(p, vec![2f64, 5f64, 1f64, 1f64]);
+    }
+}
@@ -0,0 +1,173 @@
+use nalgebra as na;
+
+use na::DMatrix;
+use na::DVector;
+use na::Matrix;
+use na::Vector;
+use na::base::dimension as dim;
+use na::base::VecStorage;
+
+use super::Layer;
+
+/// Layer applied to every step of a sequence.
+///
+/// Input is the sequence of `STEPS` inputs of `L` concatenated into
+/// one vector. All time steps share the same parameters of `L`.
+pub struct TimeDistributed<L: Layer, const STEPS: usize> {
+    steps: Vec<L>,
+    pub signal: Vector::<f64, dim::Dyn,
+                         VecStorage::<f64, dim::Dyn, dim::U1>>,
+    chain_element: Matrix::<f64, dim::Dyn, dim::Dyn,
+                            VecStorage::<f64, dim::Dyn, dim::Dyn>>,
+}
+
+impl<L: Layer + Default, const STEPS: usize> TimeDistributed<L, STEPS> {
+    pub fn new() -> Self {
+        Self {
+            steps: (0..STEPS).map(|_| L::default()).collect(),
+            signal: Vector::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                 dim::U1, 0f64),
+            chain_element: Matrix::from_element_generic(dim::Dyn(Self::NEURONS_OUT),
+                                                        dim::Dyn(Self::NEURONS_IN), 0f64),
+        }
+    }
+}
+
+impl<L: Layer + Default, const STEPS: usize> Default for TimeDistributed<L, STEPS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: Layer, const STEPS: usize> Layer for TimeDistributed<L, STEPS> {
+    const PARAMS_CNT: usize = L::PARAMS_CNT;
+    const NEURONS_IN: usize = L::NEURONS_IN * STEPS;
+    const NEURONS_OUT: usize = L::NEURONS_OUT * STEPS;
+
+    unsafe fn eval_unchecked(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        for t in 0..STEPS {
+            y.rows_mut(t*L::NEURONS_OUT, L::NEURONS_OUT).copy_from(
+                &L::eval_unchecked(
+                    p, x.rows(t*L::NEURONS_IN, L::NEURONS_IN).into_owned()));
+        }
+
+        y
+    }
+
+    fn eval(p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        let mut y: DVector<f64> = DVector::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT), dim::U1, 0f64);
+        for t in 0..STEPS {
+            y.rows_mut(t*L::NEURONS_OUT, L::NEURONS_OUT).copy_from(
+                &L::eval(
+                    p, x.rows(t*L::NEURONS_IN, L::NEURONS_IN).into_owned()));
+        }
+
+        y
+    }
+
+    fn forward(&mut self, p: &[f64], x: DVector<f64>) -> DVector<f64> {
+        assert_eq!(p.len(), Self::PARAMS_CNT);
+        assert_eq!(x.len(), Self::NEURONS_IN);
+
+        for (t, layer) in self.steps.iter_mut().enumerate() {
+            let y = layer.forward(
+                p, x.rows(t*L::NEURONS_IN, L::NEURONS_IN).into_owned());
+            self.signal.rows_mut(t*L::NEURONS_OUT, L::NEURONS_OUT)
+                .copy_from(&y);
+        }
+
+        self.signal.clone()
+    }
+
+    fn backward(&mut self, p: &[f64]) {
+        for (t, layer) in self.steps.iter_mut().enumerate() {
+            layer.backward(p);
+            self.chain_element.view_mut(
+                (t*L::NEURONS_OUT, t*L::NEURONS_IN),
+                (L::NEURONS_OUT, L::NEURONS_IN))
+                .copy_from(layer.chain_element());
+        }
+    }
+
+    fn chain_element(&self) -> &DMatrix<f64> {
+        &self.chain_element
+    }
+
+    fn chain_end(&self, x: &DVector<f64>) -> DMatrix<f64>
+    {
+        let mut matrix: DMatrix<f64> =
+            DMatrix::from_element_generic(
+            dim::Dyn(Self::NEURONS_OUT),
+            dim::Dyn(Self::PARAMS_CNT), 0f64);
+
+        for (t, layer) in self.steps.iter().enumerate() {
+            matrix.rows_mut(t*L::NEURONS_OUT, L::NEURONS_OUT).copy_from(
+                &layer.chain_end(
+                    &x.rows(t*L::NEURONS_IN, L::NEURONS_IN).into_owned()));
+        }
+
+        matrix
+    }
+
+    fn default_initial_params() -> Vec<f64> {
+        L::default_initial_params()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::LinLayer;
+    use super::super::ReLULayer;
+
+    #[test]
+    fn test_eval() {
+        let p: [f64; 2] = [2., 1.];
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -3f64].as_slice());
+
+        let y = TimeDistributed::<LinLayer<1, 1>, 2>::eval(&p, x);
+        assert_eq!(y, na::vector![3f64, -5f64]);
+    }
+
+    #[test]
+    fn test_forward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -3f64].as_slice());
+        let mut layer = TimeDistributed::<ReLULayer<1>, 2>::new();
+
+        let y = layer.forward(&[], x);
+        assert_eq!(y, na::vector![1f64, 0f64]);
+    }
+
+    #[test]
+    fn test_backward() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -3f64].as_slice());
+        let mut layer = TimeDistributed::<ReLULayer<1>, 2>::new();
+
+        let _ = layer.forward(&[], x);
+        layer.backward(&[]);
+
+        assert_eq!(*layer.chain_element(),
+                   na::matrix![1f64, 0f64;
+                               0f64, 0f64]);
+    }
+
+    #[test]
+    fn test_chain_end() {
+        let x = DVector::from_column_slice(
+            na::vector![1f64, -3f64].as_slice());
+        let layer = TimeDistributed::<LinLayer<1, 1>, 2>::new();
+
+        assert_eq!(layer.chain_end(&x),
+                   na::matrix![1f64, 1f64;
+                               -3f64, 1f64]);
+    }
+}
@@ -1,3 +1,7 @@
+// Lets code generated by `libdt-macros` refer to `::libdt`
+// inside this crate too.
+extern crate self as libdt;
+
 #[cfg(feature = "macros")]
 pub use libdt_macros as macros;
 
@@ -16,4 +16,65 @@ pub trait Network {
     fn jacobian(&mut self, x: &DVector<f64>) ->
         DMatrix<f64>;
     fn default_initial_params() -> Vec<f64>;
+
+    /// Evaluates network unrolled over sequence of inputs.
+    ///
+    /// Inputs are concatenated into single network input
+    /// and network output is split into as many outputs.
+    /// `xs.len()` must equal `STEPS` of recurrent and
+    /// `TimeDistributed` layers of the network.
+    fn eval_sequence(p: &[f64], xs: &[DVector<f64>]) ->
+        Vec<DVector<f64>>
+    {
+        assert_sequence_len::<Self>(xs);
+
+        split_sequence(Self::eval(p, concat_sequence(xs)), xs.len())
+    }
+
+    /// Jacobian of all outputs of the sequence with respect
+    /// to parameters, which are shared by all time steps.
+    /// `xs.len()` must equal `STEPS` of recurrent and
+    /// `TimeDistributed` layers of the network.
+    fn jacobian_sequence(&mut self, p: &[f64], xs: &[DVector<f64>]) ->
+        DMatrix<f64>
+    {
+        assert_sequence_len::<Self>(xs);
+
+        let x = concat_sequence(xs);
+        self.forward(p, x.clone());
+        self.backward(p);
+
+        self.jacobian(&x)
+    }
+}
+
+fn assert_sequence_len<N: Network + ?Sized>(xs: &[DVector<f64>]) {
+    assert!(!xs.is_empty(), "sequence of inputs is empty");
+    assert!(xs.iter().all(|xi| xi.len() == xs[0].len()),
+            "inputs of the sequence differ in size");
+    assert!(xs.len() * xs[0].len() == N::NEURONS_IN,
+            "sequence of {} inputs does not fit network input of size {}, \
+             its length must equal STEPS of recurrent layers",
+            xs.len(), N::NEURONS_IN);
+}
+
+/// Concatenates sequence into single vector.
+pub fn concat_sequence(xs: &[DVector<f64>]) -> DVector<f64> {
+    let mut x: Vec<f64> = Vec::new();
+    for xi in xs {
+        x.extend_from_slice(xi.as_slice());
+    }
+
+    DVector::from_vec(x)
+}
+
+/// Splits vector into sequence of `steps` equally sized vectors.
+pub fn split_sequence(x: DVector<f64>, steps: usize) -> Vec<DVector<f64>> {
+    assert!(steps > 0);
+    assert_eq!(x.len() % steps, 0);
+
+    x.as_slice()
+        .chunks(x.len() / steps)
+        .map(DVector::from_column_slice)
+        .collect()
 }
@@ -5,6 +5,8 @@ use nalgebra::base::dimension as dim;
 
 use super::super::network::Network;
 use super::Trainer;
+use super::Objective;
+use super::SquaredError;
 
 use super::common::cost;
 use super::common::apply_step;
@@ -17,6 +19,7 @@ pub struct CGTrainer<N: Network>
     x_values: Vec<DVector<f64>>,
     d_values: Vec<DVector<f64>>,
     nn: N,
+    objective: Box<dyn Objective + Send + Sync>,
 
     step_num: usize,
     prev_grad: RowDVector<f64>,
@@ -36,6 +39,16 @@ fn net_eval<N: Network>(p: &[f64], x_values: &[DVector<f64>])
     y_values
 }
 
+impl<N: Network> CGTrainer<N> {
+    /// Sets objective minimized by the trainer.
+    /// Default is `SquaredError`.
+    pub fn set_objective<O: Objective + Send + Sync + 'static>(
+        &mut self, objective: O)
+    {
+        self.objective = Box::new(objective);
+    }
+}
+
 impl<N: Network> Trainer<N> for CGTrainer<N>
 {
     fn new(nn: N, p: Vec<f64>,
@@ -50,6 +63,7 @@ impl<N: Network> Trainer<N> for CGTrainer<N>
             x_values,
             d_values,
             nn,
+            objective: Box::new(SquaredError),
 
             step_num: 0usize,
             prev_grad: Matrix::from_element_generic(
@@ -75,8 +89,8 @@ impl<N: Network> Trainer<N> for CGTrainer<N>
         }
 
         let step = choose_step::<N>(
-            &mut self.p, &self.x_values,
-            &self.d_values, direction.clone());
+            self.objective.as_ref(), &mut self.p,
+            &self.x_values, &self.d_values, direction.clone());
         apply_step(&mut self.p, &step);
 
         // Update current state.
@@ -90,7 +104,8 @@ impl<N: Network> Trainer<N> for CGTrainer<N>
             self.p.as_slice(), self.x_values.as_slice());
         let y_values = y_values.as_slice();
 
-        cost(y_values, self.d_values.as_slice())
+        cost(self.objective.as_ref(), y_values,
+             self.d_values.as_slice())
     }
 
     fn grad(&mut self) -> RowDVector<f64> {
@@ -106,7 +121,7 @@ impl<N: Network> Trainer<N> for CGTrainer<N>
                 let y = self.nn.forward(&self.p, x.clone());
                 self.nn.backward(&self.p);
                 let jm = self.nn.jacobian(x);
-                let g = 2f64 * (y - d).transpose() * jm;
+                let g = self.objective.gradient(&y, d) * jm;
     
                 grad_sum += g;
             }
@@ -122,3 +137,51 @@ impl<N: Network> Trainer<N> for CGTrainer<N>
         &self.p
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::super::super::layer::LinLayer;
+    use super::super::GaussianNLL;
+    use libdt_macros::neural_network;
+
+    use super::*;
+
+    #[neural_network]
+    struct Test1Network {
+        layers: (LinLayer::<1, 2>,)
+    }
+
+    #[test]
+    fn test_gaussian_nll_training() {
+        // Targets spread by `0.1` around `0` and by `1` around `1`.
+        let mut x_values: Vec<DVector<f64>> = Vec::new();
+        let mut d_values: Vec<DVector<f64>> = Vec::new();
+        for (x, d) in [(0f64, -0.1f64), (0., 0.1), (1., 0.), (1., 2.)] {
+            x_values.push(DVector::from_column_slice(&[x]));
+            d_values.push(DVector::from_column_slice(&[d]));
+        }
+
+        let nn = Test1Network::new();
+        let mut trainer = CGTrainer::new(
+            nn, vec![0f64; Test1Network::PARAMS_CNT],
+            x_values, d_values);
+        trainer.set_objective(GaussianNLL::<1>);
+
+        for _ in 0..200 {
+            trainer.make_step();
+        }
+
+        let y = Test1Network::eval(
+            trainer.params(), DVector::from_column_slice(&[0f64]));
+        let (lower, upper) = GaussianNLL::<1>::interval(&y, 1.);
+        assert_float_eq!(lower[0], -0.1f64, abs <= 0.001);
+        assert_float_eq!(upper[0], 0.1f64, abs <= 0.001);
+
+        let y = Test1Network::eval(
+            trainer.params(), DVector::from_column_slice(&[1f64]));
+        let (lower, upper) = GaussianNLL::<1>::interval(&y, 1.);
+        assert_float_eq!(lower[0], 0f64, abs <= 0.001);
+        assert_float_eq!(upper[0], 2f64, abs <= 0.001);
+    }
+}
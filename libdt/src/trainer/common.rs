@@ -2,15 +2,16 @@ use nalgebra::DVector;
 use nalgebra::RowDVector;
 
 use super::Network;
+use super::Objective;
 
 pub fn cost(
+    objective: &dyn Objective,
     y_values: &[DVector<f64>],
     d_values: &[DVector<f64>],
 ) -> f64 {
     let mut result: f64 = 0.;
     for (y, d) in y_values.iter().zip(d_values.iter()) {
-        assert_eq!(y.len(), d.len());
-        result += ((y - d).transpose() * (y - d)).trace();
+        result += objective.cost(y, d);
     }
 
     result
@@ -33,7 +34,8 @@ pub fn revert_step(p: &mut [f64], step: &RowDVector<f64>) {
 }
 
 pub fn eval_untouched<N: Network>
-    (p: &mut [f64], step: &RowDVector<f64>,
+    (objective: &dyn Objective,
+     p: &mut [f64], step: &RowDVector<f64>,
      x_values: &[DVector<f64>],
      d_values: &[DVector<f64>])
     -> f64
@@ -51,7 +53,7 @@ pub fn eval_untouched<N: Network>
     }
 
     revert_step(p, step);
-    cost(y_values.as_slice(), d_values)
+    cost(objective, y_values.as_slice(), d_values)
 }
 
 const P0: f64 = 0.000001f64;
@@ -61,7 +63,8 @@ const PHI2: f64 = 2.618033988749894848207f64;
 const RPHI: f64 = 0.618033988749894848207f64;
 
 pub fn choose_step<N: Network>
-    (p: &mut [f64],
+    (objective: &dyn Objective,
+     p: &mut [f64],
      x_values: &[DVector<f64>],
      d_values: &[DVector<f64>],
      direction: RowDVector<f64>)
@@ -78,12 +81,13 @@ pub fn choose_step<N: Network>
         y_values.push(N::eval(&p, x));
     }
     let y_values = y_values.as_slice();
-    fx1 = cost(y_values, d_values);
+    fx1 = cost(objective, y_values, d_values);
 
     x1 = 0.;
     x2 = P0;
     while eval_untouched::<N>
-        (p, &(x2*direction.clone()), x_values, d_values) <= fx1
+        (objective, p, &(x2*direction.clone()),
+         x_values, d_values) <= fx1
     {
         x2 = x1 + (x2 - x1)*PHI2;
     }
@@ -91,9 +95,11 @@ pub fn choose_step<N: Network>
 	x3 = x2 - (x2 - x1)*RPHI;
 	x4 = x1 + (x2 - x1)*RPHI;
 	fx3 = eval_untouched::<N>
-        (p, &(x3*direction.clone()), x_values, d_values);
+        (objective, p, &(x3*direction.clone()),
+         x_values, d_values);
 	fx4 = eval_untouched::<N>
-        (p, &(x4*direction.clone()), x_values, d_values);
+        (objective, p, &(x4*direction.clone()),
+         x_values, d_values);
 	while (x1 - x2).abs() > MAX_E {
 		if fx3 < fx4 {
 			x2 = x4;
@@ -102,7 +108,8 @@ pub fn choose_step<N: Network>
 			x3 = x2 - (x2 - x1)*RPHI;
 			x4 = x1 + (x2 - x1)*RPHI;
 			fx3 = eval_untouched::<N>
-                (p, &(x3*direction.clone()), x_values, d_values);
+                (objective, p, &(x3*direction.clone()),
+                 x_values, d_values);
 		} else {
 			x1 = x3;
 
@@ -110,7 +117,8 @@ pub fn choose_step<N: Network>
 			x3 = x2 - (x2 - x1)*RPHI;
 			x4 = x1 + (x2 - x1)*RPHI;
 			fx4 = eval_untouched::<N>
-                (p, &(x4*direction.clone()), x_values, d_values);
+                (objective, p, &(x4*direction.clone()),
+                 x_values, d_values);
 		}
 	}
 
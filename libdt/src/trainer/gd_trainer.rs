@@ -5,6 +5,8 @@ use nalgebra::base::dimension as dim;
 
 use super::super::network::Network;
 use super::Trainer;
+use super::Objective;
+use super::SquaredError;
 
 use super::common::cost;
 use super::common::apply_step;
@@ -17,6 +19,7 @@ pub struct GDTrainer<N: Network>
     x_values: Vec<DVector<f64>>,
     d_values: Vec<DVector<f64>>,
     nn: N,
+    objective: Box<dyn Objective + Send + Sync>,
 }
 
 fn net_eval<N: Network>(p: &[f64], x_values: &[DVector<f64>])
@@ -31,6 +34,16 @@ fn net_eval<N: Network>(p: &[f64], x_values: &[DVector<f64>])
     y_values
 }
 
+impl<N: Network> GDTrainer<N> {
+    /// Sets objective minimized by the trainer.
+    /// Default is `SquaredError`.
+    pub fn set_objective<O: Objective + Send + Sync + 'static>(
+        &mut self, objective: O)
+    {
+        self.objective = Box::new(objective);
+    }
+}
+
 impl<N: Network> Trainer<N> for GDTrainer<N> {
     fn new(nn: N, p: Vec<f64>,
            x_values: Vec<DVector<f64>>,
@@ -44,6 +57,7 @@ impl<N: Network> Trainer<N> for GDTrainer<N> {
             x_values,
             d_values,
             nn,
+            objective: Box::new(SquaredError),
         }
     }
 
@@ -51,8 +65,8 @@ impl<N: Network> Trainer<N> for GDTrainer<N> {
         let direction = -(self.grad()).clone();
 
         let step = choose_step::<N>(
-            &mut self.p, &self.x_values,
-            &self.d_values, direction);
+            self.objective.as_ref(), &mut self.p,
+            &self.x_values, &self.d_values, direction);
         apply_step(&mut self.p, &step);
     }
 
@@ -62,7 +76,8 @@ impl<N: Network> Trainer<N> for GDTrainer<N> {
                           self.x_values.as_slice());
         let y_values = y_values.as_slice();
 
-        cost(y_values, self.d_values.as_slice())
+        cost(self.objective.as_ref(), y_values,
+             self.d_values.as_slice())
     }
 
     fn grad(&mut self) -> RowDVector<f64> {
@@ -77,7 +92,7 @@ impl<N: Network> Trainer<N> for GDTrainer<N> {
                 let y = self.nn.forward(&self.p, x.clone());
                 self.nn.backward(&self.p);
                 let jm = self.nn.jacobian(x);
-                let g = 2f64 * (y - d).transpose() * jm;
+                let g = self.objective.gradient(&y, d) * jm;
     
                 grad_sum += g;
             }
@@ -93,3 +108,93 @@ impl<N: Network> Trainer<N> for GDTrainer<N> {
         &self.p
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::super::super::layer::LinLayer;
+    use super::super::super::layer::MixtureDensityLayer;
+    use super::super::MixtureNLL;
+    use libdt_macros::neural_network;
+
+    use super::*;
+
+    #[neural_network]
+    struct Test1Network {
+        layers: (LinLayer::<1, 6>,
+                 MixtureDensityLayer::<2>)
+    }
+
+    // Every input has two equally likely targets, `-1` and `1`.
+    fn bimodal_data() -> (Vec<DVector<f64>>, Vec<DVector<f64>>) {
+        let mut x_values: Vec<DVector<f64>> = Vec::new();
+        let mut d_values: Vec<DVector<f64>> = Vec::new();
+        for i in 0..10 {
+            for d in [-1f64, 1f64] {
+                x_values.push(DVector::from_column_slice(
+                    &[i as f64 / 10.]));
+                d_values.push(DVector::from_column_slice(&[d]));
+            }
+        }
+
+        (x_values, d_values)
+    }
+
+    fn test_params() -> Vec<f64> {
+        (0..Test1Network::PARAMS_CNT)
+            .map(|i| ((i*7 % 11) as f64 - 5.) / 10.)
+            .collect()
+    }
+
+    #[test]
+    fn test_mixture_nll_grad() {
+        let (x_values, d_values) = bimodal_data();
+
+        let nn = Test1Network::new();
+        let mut trainer = GDTrainer::new(
+            nn, test_params(), x_values, d_values);
+        trainer.set_objective(MixtureNLL::<2>);
+
+        let g = trainer.grad();
+
+        const H: f64 = 0.000_001;
+        for j in 0..Test1Network::PARAMS_CNT {
+            trainer.p[j] += H;
+            let c1 = trainer.cost();
+            trainer.p[j] -= 2.*H;
+            let c2 = trainer.cost();
+            trainer.p[j] += H;
+
+            assert_float_eq!(g[j], (c1 - c2) / (2.*H), abs <= 0.000_01);
+        }
+    }
+
+    #[test]
+    fn test_mixture_nll_training() {
+        let (x_values, d_values) = bimodal_data();
+
+        let nn = Test1Network::new();
+        let mut trainer = GDTrainer::new(
+            nn, test_params(), x_values, d_values);
+        trainer.set_objective(MixtureNLL::<2>);
+
+        for _ in 0..200 {
+            trainer.make_step();
+        }
+
+        // Means of components should settle on both modes
+        // instead of their average.
+        let x = DVector::from_column_slice(&[0.5f64]);
+        let y = Test1Network::eval(trainer.params(), x);
+        let (m1, m2) = (y[2].min(y[3]), y[2].max(y[3]));
+        assert_float_eq!(m1, -1f64, abs <= 0.1);
+        assert_float_eq!(m2, 1f64, abs <= 0.1);
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<GDTrainer<Test1Network>>();
+    }
+}
@@ -10,8 +10,13 @@ use super::super::trainer::Trainer;
 use super::common::cost;
 use super::common::apply_step;
 use super::common::eval_untouched;
+use super::Objective;
+use super::SquaredError;
 
 /// Trainer using Levenberg-Marquardt Method.
+///
+/// Levenberg-Marquardt steps assume sum of squared errors,
+/// so this trainer always minimizes `SquaredError`.
 pub struct LMTrainer<N: Network>
 {
     p: Vec<f64>,
@@ -44,7 +49,7 @@ impl<N: Network> LMTrainer<N> {
         assert_eq!(g.len(), N::PARAMS_CNT);
 
         let current_cost = eval_untouched::<N>(
-            &mut self.p,
+            &SquaredError, &mut self.p,
             &Matrix::from_element_generic(
                 dim::U1, dim::Dyn(N::PARAMS_CNT), 0f64),
             self.x_values.as_slice(),
@@ -53,24 +58,44 @@ impl<N: Network> LMTrainer<N> {
         loop {
             let mut m = h.clone();
             for i in 0..m.ncols() {
+                // Parameters which do not affect the output (frozen
+                // ones, like these of `StandardizeLayer`) have zero row
+                // and column in `h`, so multiplicative damping leaves
+                // `m` singular and the whole step would fall back to
+                // gradient descent. Damp them additively instead;
+                // their gradient is zero, so they are left unchanged.
+                if m[(i, i)] == 0f64 {
+                    m[(i, i)] = self.lambda;
+                    continue;
+                }
+
                 m[(i, i)] += self.lambda*m[(i, i)];
             }
             let m = m.try_inverse();
             let step = match m {
                 Some(m) => -(&m * g.transpose()).transpose(),
                 None => return super::common::choose_step::<N>(
-                        &mut self.p, self.x_values.as_slice(),
+                        &SquaredError, &mut self.p,
+                        self.x_values.as_slice(),
                         self.d_values.as_slice(), -g),
             };
             
+            let predicted = (step.clone() *
+                (self.lambda*
+                 DMatrix::from_diagonal(&h.diagonal())*
+                 step.transpose() - g.transpose())).norm();
+
+            // Nothing is left to reduce, so no damping would give
+            // an acceptable step and `rho` would not be a number.
+            if step.norm() == 0f64 ||
+               (current_cost == 0f64 && predicted == 0f64) {
+                break step;
+            }
+
             let rho = (current_cost - eval_untouched::<N>(
-                &mut self.p, &step,
+                &SquaredError, &mut self.p, &step,
                 self.x_values.as_slice(),
-                self.d_values.as_slice())) /
-                (step.clone() *
-                 (self.lambda*
-                  DMatrix::from_diagonal(&h.diagonal())*
-                  step.transpose() - g.transpose())).norm();
+                self.d_values.as_slice())) / predicted;
 
             if rho > 0.1f64 {
                 let new_lambda = self.lambda / 9f64;
@@ -107,7 +132,7 @@ impl<N: Network> LMTrainer<N> {
                 self.nn.backward(&self.p);
                 let jm = self.nn.jacobian(x);
 
-                g_sum += 2f64 * (y - d).transpose() * jm.clone();
+                g_sum += SquaredError.gradient(&y, d) * jm.clone();
                 jm_sum += jm;
             }
             
@@ -146,7 +171,7 @@ impl<N: Network> Trainer<N> for LMTrainer<N> {
             self.p.as_slice(), self.x_values.as_slice());
         let y_values = y_values.as_slice();
 
-        cost(y_values, self.d_values.as_slice())
+        cost(&SquaredError, y_values, self.d_values.as_slice())
     }
 
     fn grad(&mut self) -> RowDVector<f64>
@@ -162,7 +187,7 @@ impl<N: Network> Trainer<N> for LMTrainer<N> {
                 let y = self.nn.forward(&self.p, x.clone());
                 self.nn.backward(&self.p);
                 let jm = self.nn.jacobian(x);
-                let g = 2f64 * (y - d).transpose() * jm;
+                let g = SquaredError.gradient(&y, d) * jm;
     
                 grad_sum += g;
             }
@@ -183,10 +208,12 @@ impl<N: Network> Trainer<N> for LMTrainer<N> {
 mod tests {
     use rand::Rng;
     use float_eq::assert_float_eq;
-    use super::super::super::layer::Layer;
     use super::super::super::layer::LinLayer;
     use super::super::super::layer::SigmaLayer;
+    use super::super::super::layer::ReLULayer;
     use super::super::super::layer::SumLayer;
+    use super::super::super::layer::StandardizeLayer;
+    use super::super::super::layer::SplineLayer;
     use super::super::super::network::Network;
     use libdt_macros::neural_network;
 
@@ -286,4 +313,127 @@ mod tests {
                    nalgebra::vector![21f64, 24f64,
                                      -10.5f64, -12f64]);
     }
+
+    #[neural_network]
+    struct Test4Network {
+        layers: (StandardizeLayer::<1>,
+                 LinLayer::<1, 1>)
+    }
+
+    #[test]
+    fn test_frozen_params() {
+        let x_values: Vec<DVector<f64>> =
+            vec![DVector::from_column_slice(
+                     nalgebra::vector![100f64].as_slice()),
+                 DVector::from_column_slice(
+                     nalgebra::vector![300f64].as_slice())];
+        let d_values: Vec<DVector<f64>> =
+            vec![DVector::from_column_slice(
+                     nalgebra::vector![1f64].as_slice()),
+                 DVector::from_column_slice(
+                     nalgebra::vector![5f64].as_slice())];
+
+        let mut p = StandardizeLayer::<1>::params_from_data(&x_values);
+        p.append(&mut vec![0f64, 0f64]);
+
+        let nn = Test4Network::new();
+        let mut trainer = LMTrainer::new(
+            nn, p, x_values, d_values);
+
+        for _ in 0..100 {
+            trainer.make_step();
+        }
+
+        assert_eq!(&trainer.params()[..2], &[200f64, 100f64]);
+        assert_float_eq!(trainer.params()[2], 2f64, abs <= 0.000_1);
+        assert_float_eq!(trainer.params()[3], 3f64, abs <= 0.000_1);
+    }
+
+    #[neural_network]
+    struct Test5Network {
+        layers: (SplineLayer::<1, 1, 5>,)
+    }
+
+    #[test]
+    fn test_spline_fit() {
+        let mut x_values: Vec<DVector<f64>> = Vec::new();
+        let mut d_values: Vec<DVector<f64>> = Vec::new();
+        for i in 0..=20 {
+            let x = i as f64 / 10. - 1.;
+            x_values.push(DVector::from_column_slice(&[x]));
+            d_values.push(DVector::from_column_slice(&[x*x]));
+        }
+
+        let p = vec![0f64; Test5Network::PARAMS_CNT];
+        let nn = Test5Network::new();
+        let mut trainer = LMTrainer::new(
+            nn, p, x_values, d_values);
+
+        for _ in 0..200 {
+            if trainer.cost() < 0.000_001 {
+                break;
+            }
+            trainer.make_step();
+        }
+
+        // Cubic splines represent `x^2` exactly.
+        assert_float_eq!(trainer.cost(), 0f64, abs <= 0.000_001);
+    }
+
+    #[test]
+    fn test_exact_fit() {
+        let x_values: Vec<DVector<f64>> =
+            vec![DVector::from_column_slice(
+                     nalgebra::vector![3f64].as_slice())];
+        let d_values: Vec<DVector<f64>> =
+            vec![DVector::from_column_slice(
+                     nalgebra::vector![6f64].as_slice())];
+
+        let p: Vec<f64> = vec![2f64];
+
+        let nn = Test2Network::new();
+        let mut trainer = LMTrainer::new(
+            nn, p, x_values, d_values);
+
+        trainer.make_step();
+
+        assert_eq!(trainer.params(), &[2f64]);
+        assert_eq!(trainer.lambda, 0.1f64);
+    }
+
+    #[neural_network]
+    struct Test6Network {
+        layers: (LinLayer::<1, 2>,
+                 ReLULayer::<2>,
+                 LinLayer::<2, 1>)
+    }
+
+    #[test]
+    fn test_dead_relu() {
+        let mut x_values: Vec<DVector<f64>> = Vec::new();
+        let mut d_values: Vec<DVector<f64>> = Vec::new();
+        for i in 0..=10 {
+            let x = i as f64 / 10.;
+            x_values.push(DVector::from_column_slice(&[x]));
+            d_values.push(DVector::from_column_slice(&[2.*x + 1.]));
+        }
+
+        // Second hidden unit never activates, so its parameters
+        // get zero diagonal in `h`.
+        let p: Vec<f64> = vec![1f64, 0f64, 1f64, -1f64,
+                               0.5f64, 0.5f64, 0f64];
+        let nn = Test6Network::new();
+        let mut trainer = LMTrainer::new(
+            nn, p, x_values, d_values);
+
+        for _ in 0..100 {
+            trainer.make_step();
+        }
+
+        assert!(trainer.params().iter().all(|pi| pi.is_finite()));
+        assert_eq!(trainer.params()[1], 0f64);
+        assert_eq!(trainer.params()[3], -1f64);
+        assert_eq!(trainer.params()[5], 0.5f64);
+        assert_float_eq!(trainer.cost(), 0f64, abs <= 0.000_001);
+    }
 }
@@ -18,6 +18,9 @@ pub trait Trainer<N: Network> {
 
 mod common;
 
+mod objective;
+pub use objective::*;
+
 mod gd_trainer;
 pub use gd_trainer::*;
 
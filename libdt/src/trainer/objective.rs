@@ -0,0 +1,252 @@
+use nalgebra::DVector;
+use nalgebra::RowDVector;
+
+/// Training objective minimized by trainers.
+///
+/// Total cost is sum of `cost` over all samples.
+pub trait Objective {
+    /// Cost of network output `y` for desired output `d`.
+    fn cost(&self, y: &DVector<f64>, d: &DVector<f64>) -> f64;
+
+    /// Derivative of `cost` over `y`.
+    fn gradient(&self, y: &DVector<f64>, d: &DVector<f64>)
+        -> RowDVector<f64>;
+}
+
+/// Sum of squared errors, `|y - d|^2`.
+pub struct SquaredError;
+
+impl Objective for SquaredError {
+    fn cost(&self, y: &DVector<f64>, d: &DVector<f64>) -> f64 {
+        assert_eq!(y.len(), d.len());
+
+        (y - d).norm_squared()
+    }
+
+    fn gradient(&self, y: &DVector<f64>, d: &DVector<f64>)
+        -> RowDVector<f64>
+    {
+        assert_eq!(y.len(), d.len());
+
+        2f64 * (y - d).transpose()
+    }
+}
+
+/// Negative log-likelihood of a scalar target under a mixture
+/// of `K` Gaussians.
+///
+/// Network output is expected in the layout of `MixtureDensityLayer`:
+/// `K` mixture weights, `K` means and `K` variances.
+pub struct MixtureNLL<const K: usize>;
+
+impl<const K: usize> MixtureNLL<K> {
+    /// Returns log-densities `ln(w_k N(d; m_k, v_k))`
+    /// of all components and their log-sum-exp.
+    fn log_densities(y: &DVector<f64>, d: &DVector<f64>)
+        -> (Vec<f64>, f64)
+    {
+        assert_eq!(y.len(), 3 * K);
+        assert_eq!(d.len(), 1);
+
+        let log_n: Vec<f64> = (0..K).map(|k| {
+            let (w, m, v) = (y[k], y[K + k], y[2*K + k]);
+            let e = d[0] - m;
+
+            w.ln() - e*e / (2.*v) -
+                0.5 * (2. * std::f64::consts::PI * v).ln()
+        }).collect();
+
+        let max = log_n.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let lse = max + log_n.iter().map(|l| (l - max).exp())
+            .sum::<f64>().ln();
+
+        (log_n, lse)
+    }
+}
+
+impl<const K: usize> Objective for MixtureNLL<K> {
+    fn cost(&self, y: &DVector<f64>, d: &DVector<f64>) -> f64 {
+        -Self::log_densities(y, d).1
+    }
+
+    fn gradient(&self, y: &DVector<f64>, d: &DVector<f64>)
+        -> RowDVector<f64>
+    {
+        let (log_n, lse) = Self::log_densities(y, d);
+
+        let mut g = RowDVector::zeros(3 * K);
+        for k in 0..K {
+            // Posterior probability of component `k`.
+            let r = (log_n[k] - lse).exp();
+            let (w, m, v) = (y[k], y[K + k], y[2*K + k]);
+            let e = d[0] - m;
+
+            g[k] = -r / w;
+            g[K + k] = -r * e / v;
+            g[2*K + k] = -r * (e*e / (2.*v*v) - 1. / (2.*v));
+        }
+
+        g
+    }
+}
+
+/// Gaussian negative log-likelihood with predicted variance.
+///
+/// Network output is `D` means followed by `D` log-variances,
+/// one pair per target dimension, so every prediction comes
+/// with its own uncertainty.
+pub struct GaussianNLL<const D: usize>;
+
+impl<const D: usize> GaussianNLL<D> {
+    /// Returns predicted means.
+    pub fn mean(y: &DVector<f64>) -> DVector<f64> {
+        assert_eq!(y.len(), 2 * D);
+
+        y.rows(0, D).into_owned()
+    }
+
+    /// Returns predicted standard deviations.
+    pub fn std(y: &DVector<f64>) -> DVector<f64> {
+        assert_eq!(y.len(), 2 * D);
+
+        y.rows(D, D).map(|s| (s / 2.).exp())
+    }
+
+    /// Returns lower and upper bounds of prediction interval
+    /// `mean -+ z std`, e.g. `z = 1.96` for 95% interval.
+    pub fn interval(y: &DVector<f64>, z: f64)
+        -> (DVector<f64>, DVector<f64>)
+    {
+        let mean = Self::mean(y);
+        let std = Self::std(y);
+
+        (&mean - z * &std, &mean + z * &std)
+    }
+}
+
+impl<const D: usize> Objective for GaussianNLL<D> {
+    fn cost(&self, y: &DVector<f64>, d: &DVector<f64>) -> f64 {
+        assert_eq!(y.len(), 2 * D);
+        assert_eq!(d.len(), D);
+
+        (0..D).map(|i| {
+            let (m, s) = (y[i], y[D + i]);
+            let e = d[i] - m;
+
+            0.5 * (s + e*e * (-s).exp() +
+                   (2. * std::f64::consts::PI).ln())
+        }).sum()
+    }
+
+    fn gradient(&self, y: &DVector<f64>, d: &DVector<f64>)
+        -> RowDVector<f64>
+    {
+        assert_eq!(y.len(), 2 * D);
+        assert_eq!(d.len(), D);
+
+        let mut g = RowDVector::zeros(2 * D);
+        for i in 0..D {
+            let (m, s) = (y[i], y[D + i]);
+            let e = d[i] - m;
+
+            g[i] = -e * (-s).exp();
+            g[D + i] = 0.5 * (1. - e*e * (-s).exp());
+        }
+
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use super::*;
+
+    fn check_gradient(objective: &dyn Objective,
+                      y: &DVector<f64>, d: &DVector<f64>) {
+        let g = objective.gradient(y, d);
+
+        const H: f64 = 0.000_001;
+        for j in 0..y.len() {
+            let mut y1 = y.clone();
+            let mut y2 = y.clone();
+            y1[j] += H;
+            y2[j] -= H;
+            let dc = (objective.cost(&y1, d) -
+                      objective.cost(&y2, d)) / (2.*H);
+            assert_float_eq!(g[j], dc, abs <= 0.000_001);
+        }
+    }
+
+    #[test]
+    fn test_squared_error() {
+        let y = DVector::from_column_slice(
+            nalgebra::vector![1f64, 2f64].as_slice());
+        let d = DVector::from_column_slice(
+            nalgebra::vector![0f64, 4f64].as_slice());
+
+        assert_eq!(SquaredError.cost(&y, &d), 5f64);
+        assert_eq!(SquaredError.gradient(&y, &d),
+                   nalgebra::matrix![2f64, -4f64]);
+    }
+
+    #[test]
+    fn test_mixture_nll() {
+        // Single standard normal component.
+        let y = DVector::from_column_slice(
+            nalgebra::vector![1f64, 0f64, 1f64].as_slice());
+        let d = DVector::from_column_slice(
+            nalgebra::vector![1f64].as_slice());
+
+        assert_float_eq!(MixtureNLL::<1>.cost(&y, &d),
+                         0.5 + 0.5 * (2. * std::f64::consts::PI).ln(),
+                         abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_mixture_nll_gradient() {
+        let y = DVector::from_column_slice(
+            nalgebra::vector![0.3f64, 0.7f64,
+                              -1f64, 0.5f64,
+                              0.4f64, 2f64].as_slice());
+        let d = DVector::from_column_slice(
+            nalgebra::vector![0.2f64].as_slice());
+
+        check_gradient(&MixtureNLL::<2>, &y, &d);
+    }
+
+    #[test]
+    fn test_gaussian_nll() {
+        // Standard normal prediction.
+        let y = DVector::from_column_slice(
+            nalgebra::vector![0f64, 0f64].as_slice());
+        let d = DVector::from_column_slice(
+            nalgebra::vector![1f64].as_slice());
+
+        assert_float_eq!(GaussianNLL::<1>.cost(&y, &d),
+                         0.5 + 0.5 * (2. * std::f64::consts::PI).ln(),
+                         abs <= 0.000_000_1);
+    }
+
+    #[test]
+    fn test_gaussian_nll_gradient() {
+        let y = DVector::from_column_slice(
+            nalgebra::vector![0.3f64, -1f64, 0.5f64, -0.2f64].as_slice());
+        let d = DVector::from_column_slice(
+            nalgebra::vector![1f64, -0.5f64].as_slice());
+
+        check_gradient(&GaussianNLL::<2>, &y, &d);
+    }
+
+    #[test]
+    fn test_gaussian_nll_interval() {
+        let y = DVector::from_column_slice(
+            nalgebra::vector![1f64, -2f64, 0f64, 4f64.ln()].as_slice());
+
+        let (lower, upper) = GaussianNLL::<2>::interval(&y, 2.);
+        assert_float_eq!(lower[0], -1f64, abs <= 0.000_000_1);
+        assert_float_eq!(upper[0], 3f64, abs <= 0.000_000_1);
+        assert_float_eq!(lower[1], -6f64, abs <= 0.000_000_1);
+        assert_float_eq!(upper[1], 2f64, abs <= 0.000_000_1);
+    }
+}
@@ -1,8 +1,42 @@
-use libdt::layer::Layer;
 use libdt::layer::LinLayer;
 use libdt::layer::SigmaLayer;
+use libdt::layer::ReLULayer;
+use libdt::layer::PReLULayer;
+use libdt::layer::ParamSigmaLayer;
+use libdt::layer::Conv1DLayer;
+use libdt::layer::Conv2DLayer;
+use libdt::layer::MaxPool2DLayer;
+use libdt::layer::AvgPool2DLayer;
+use libdt::layer::SoftMaxLayer;
+use libdt::layer::Chain;
+use libdt::layer::Residual;
+use libdt::layer::Parallel;
+use libdt::layer::ElmanLayer;
+use libdt::layer::TimeDistributed;
+use libdt::layer::GRULayer;
+use libdt::layer::LSTMLayer;
+use libdt::layer::SelfAttentionLayer;
+use libdt::layer::EmbeddingLayer;
+use libdt::layer::RBFLayer;
+use libdt::layer::PolynomialFeaturesLayer;
+use libdt::layer::FourierFeaturesLayer;
+use libdt::layer::ChebyshevLayer;
+use libdt::layer::LowRankLinLayer;
+use libdt::layer::TanhLayer;
+use libdt::layer::SoftPlusLayer;
+use libdt::layer::SwishLayer;
+use libdt::layer::GaussianLayer;
+use libdt::layer::MaxoutLayer;
+use libdt::layer::GLULayer;
+use libdt::layer::ScaleShiftLayer;
+use libdt::layer::L2NormalizeLayer;
+use libdt::layer::MixtureDensityLayer;
+use libdt::layer::SplineLayer;
+use libdt::layer::LayerNormLayer;
 use libdt::network::Network;
+use libdt::network::concat_sequence;
 use libdt_macros::neural_network;
+use libdt_macros::Network;
 
 use nalgebra::DVector;
 use nalgebra::DMatrix;
@@ -11,6 +45,61 @@ use nalgebra::matrix;
 
 use float_eq::assert_float_eq;
 
+fn numeric_jacobian<N: Network>(p: &[f64], x: &DVector<f64>) ->
+    DMatrix<f64>
+{
+    const H: f64 = 0.000_001;
+
+    let mut jm: DMatrix<f64> =
+        DMatrix::zeros(N::NEURONS_OUT, N::PARAMS_CNT);
+    let mut p = p.to_vec();
+    for i in 0..N::PARAMS_CNT {
+        let pi = p[i];
+        p[i] = pi + H;
+        let y1 = N::eval(&p, x.clone());
+        p[i] = pi - H;
+        let y2 = N::eval(&p, x.clone());
+        p[i] = pi;
+
+        jm.set_column(i, &((y1 - y2) / (2f64*H)));
+    }
+
+    jm
+}
+
+/// Deterministic, irregular parameters in `[-5 / scale, 5 / scale]`.
+fn test_params<N: Network>(scale: f64) -> Vec<f64> {
+    (0..N::PARAMS_CNT)
+        .map(|i| ((i*7 % 11) as f64 - 5f64) / scale)
+        .collect()
+}
+
+fn check_jacobian<N: Network>(nn: &mut N, p: &[f64], x: &DVector<f64>) {
+    nn.forward(p, x.clone());
+    nn.backward(p);
+    let jm = nn.jacobian(x);
+
+    let result = numeric_jacobian::<N>(p, x);
+    assert_eq!(result.ncols(), jm.ncols());
+    assert_eq!(result.nrows(), jm.nrows());
+    for i in 0..result.nrows() {
+        for j in 0..result.ncols() {
+            assert_float_eq!(result[(i, j)], jm[(i, j)], abs <= 0.000_001);
+        }
+    }
+}
+
+/// Checks Jacobian of `N` with parameters from `test_params`
+/// at every input.
+fn check_network<N: Network>(scale: f64, x_values: &[DVector<f64>]) {
+    let p = test_params::<N>(scale);
+    let mut nn = N::new();
+
+    for x in x_values.iter() {
+        check_jacobian(&mut nn, &p, x);
+    }
+}
+
 #[neural_network]
 struct Test1Network {
     layers: (LinLayer::<1, 2>,
@@ -117,3 +206,438 @@ fn test_network_3() {
         }
     }
 }
+
+#[neural_network]
+struct Test4Network {
+    layers: (LinLayer::<1, 2>,
+             ReLULayer::<2>)
+}
+
+#[test]
+fn test_network_4() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64].as_slice()),
+        DVector::from_column_slice(vector![3f64].as_slice())];
+
+    let p: Vec<f64> = vec![0.5f64, -0.35f64, 2f64, 1f64];
+    let mut nn = Test4Network::new();
+
+    nn.forward(&p, x_values[0].clone());
+    nn.backward(&p);
+    let jm = nn.jacobian(&x_values[0]);
+    assert_eq!(jm, matrix![1f64, 0f64, 1f64, 0f64;
+                           0f64, 1f64, 0f64, 1f64]);
+
+    nn.forward(&p, x_values[1].clone());
+    nn.backward(&p);
+    let jm = nn.jacobian(&x_values[1]);
+    assert_eq!(jm, matrix![3f64, 0f64, 1f64, 0f64;
+                           0f64, 0f64, 0f64, 0f64]);
+}
+
+#[neural_network]
+struct Test5Network {
+    layers: (LinLayer::<2, 3>,
+             PReLULayer::<3>,
+             LinLayer::<3, 2>,
+             ParamSigmaLayer::<2>)
+}
+
+#[test]
+fn test_network_5() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice())];
+
+    let p: Vec<f64> = vec![0.7f64, -1.2f64, 0.4f64,
+                           0.9f64, 0.25f64, -1.1f64,
+                           0.1f64, -0.3f64, 0.05f64,
+                           0.2f64, 0.3f64, 0.15f64,
+                           0.5f64, -0.8f64, 1.3f64,
+                           0.6f64, -0.4f64, 0.9f64,
+                           0.2f64, -0.1f64,
+                           1.5f64, 0.7f64, -0.3f64, 0.4f64];
+    assert_eq!(p.len(), Test5Network::PARAMS_CNT);
+    let mut nn = Test5Network::new();
+
+    for x in x_values.iter() {
+        check_jacobian(&mut nn, &p, x);
+    }
+}
+
+#[neural_network]
+struct Test6Network {
+    layers: (Conv1DLayer::<6, 1, 2, 3, 1, 1>,
+             SigmaLayer::<12>,
+             Conv1DLayer::<6, 2, 1, 2, 2>,
+             LinLayer::<3, 1>)
+}
+
+#[test]
+fn test_network_6() {
+    let x = DVector::from_column_slice(
+        vector![0.1f64, -0.4f64, 0.8f64, 0.3f64, -0.2f64, 0.5f64].as_slice());
+
+    check_network::<Test6Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test7Network {
+    layers: (Conv2DLayer::<4, 4, 1, 2, 3, 1, 1>,
+             SigmaLayer::<32>,
+             MaxPool2DLayer::<4, 4, 2, 2>,
+             Conv2DLayer::<2, 2, 2, 2, 2, 1, 1>,
+             AvgPool2DLayer::<3, 3, 2, 3>,
+             LinLayer::<2, 3>,
+             SoftMaxLayer::<3>)
+}
+
+#[test]
+fn test_network_7() {
+    let mut x = DVector::zeros(16);
+    for i in 0..16 {
+        x[i] = ((i*5 % 7) as f64 - 3f64) / 4f64;
+    }
+
+    // Perturbed to keep all parameters distinct.
+    let p: Vec<f64> = test_params::<Test7Network>(5f64).iter().enumerate()
+        .map(|(i, pi)| pi + i as f64 / 100f64)
+        .collect();
+    let mut nn = Test7Network::new();
+
+    check_jacobian(&mut nn, &p, &x);
+}
+
+#[neural_network]
+struct Test8Network {
+    layers: (LinLayer::<2, 3>,
+             Residual::<Chain::<SigmaLayer::<3>, LinLayer::<3, 3>>>,
+             Residual::<PReLULayer::<3>>,
+             LinLayer::<3, 1>)
+}
+
+#[test]
+fn test_network_8() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice())];
+
+    check_network::<Test8Network>(5f64, &x_values);
+}
+
+#[neural_network]
+struct Test9Network {
+    layers: (LinLayer::<2, 3>,
+             Parallel::<LinLayer::<3, 2>, Chain::<SigmaLayer::<3>,
+                                                  LinLayer::<3, 2>>>,
+             LinLayer::<4, 1>)
+}
+
+#[test]
+fn test_network_9() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice())];
+
+    check_network::<Test9Network>(5f64, &x_values);
+}
+
+#[neural_network]
+struct Test10Network {
+    layers: (TimeDistributed::<LinLayer::<2, 3>, 4>,
+             ElmanLayer::<3, 2, 4>,
+             TimeDistributed::<LinLayer::<2, 1>, 4>)
+}
+
+#[test]
+fn test_network_10() {
+    let xs: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice()),
+        DVector::from_column_slice(vector![0.4f64, 0.9f64].as_slice()),
+        DVector::from_column_slice(vector![-0.1f64, -1.5f64].as_slice())];
+
+    let p = test_params::<Test10Network>(10f64);
+    let mut nn = Test10Network::new();
+
+    let ys = Test10Network::eval_sequence(&p, &xs);
+    assert_eq!(ys.len(), 4);
+    for y in ys.iter() {
+        assert_eq!(y.len(), 1);
+    }
+
+    let x = concat_sequence(&xs);
+    let jm = nn.jacobian_sequence(&p, &xs);
+    let result = numeric_jacobian::<Test10Network>(&p, &x);
+    assert_eq!(result.ncols(), jm.ncols());
+    assert_eq!(result.nrows(), jm.nrows());
+    for i in 0..result.nrows() {
+        for j in 0..result.ncols() {
+            assert_float_eq!(result[(i, j)], jm[(i, j)], abs <= 0.000_001);
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "STEPS")]
+fn test_network_10_wrong_steps() {
+    let xs: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice())];
+
+    let p = test_params::<Test10Network>(10f64);
+    let _ = Test10Network::eval_sequence(&p, &xs);
+}
+
+#[test]
+#[should_panic(expected = "differ in size")]
+fn test_network_10_ragged_steps() {
+    let xs: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64, 0.7f64].as_slice()),
+        DVector::from_column_slice(vector![0.4f64].as_slice()),
+        DVector::from_column_slice(vector![-0.1f64, -1.5f64].as_slice())];
+
+    let p = test_params::<Test10Network>(10f64);
+    let _ = Test10Network::eval_sequence(&p, &xs);
+}
+
+#[neural_network]
+struct Test11Network {
+    layers: (GRULayer::<2, 3, 3>,
+             LSTMLayer::<3, 2, 3>,
+             TimeDistributed::<LinLayer::<2, 1>, 3>)
+}
+
+#[test]
+fn test_network_11() {
+    let xs: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-2f64, 0.3f64].as_slice()),
+        DVector::from_column_slice(vector![0.4f64, 0.9f64].as_slice())];
+
+    check_network::<Test11Network>(5f64, &[concat_sequence(&xs)]);
+}
+
+#[neural_network]
+struct Test12Network {
+    layers: (TimeDistributed::<LinLayer::<1, 2>, 4>,
+             Residual::<SelfAttentionLayer::<4, 2>>,
+             LinLayer::<8, 3>,
+             SoftMaxLayer::<3>)
+}
+
+#[test]
+fn test_network_12() {
+    let x = DVector::from_column_slice(
+        vector![0.1f64, -0.4f64, 0.8f64, 0.3f64].as_slice());
+
+    check_network::<Test12Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test13Network {
+    layers: (TimeDistributed::<EmbeddingLayer::<4, 2>, 2>,
+             SigmaLayer::<4>,
+             LinLayer::<4, 1>)
+}
+
+#[test]
+fn test_network_13() {
+    let x = DVector::from_column_slice(vector![3f64, 1f64].as_slice());
+
+    check_network::<Test13Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test14Network {
+    layers: (RBFLayer::<2, 4>,
+             LinLayer::<4, 1>)
+}
+
+#[test]
+fn test_network_14() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![1f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-0.2f64, 0.3f64].as_slice())];
+
+    // Shifted to keep all parameters positive.
+    let p: Vec<f64> = test_params::<Test14Network>(5f64).iter()
+        .map(|pi| pi + 1.2f64)
+        .collect();
+    let mut nn = Test14Network::new();
+
+    for x in x_values.iter() {
+        check_jacobian(&mut nn, &p, x);
+    }
+}
+
+#[neural_network]
+struct Test15Network {
+    layers: (LinLayer::<2, 2>,
+             PolynomialFeaturesLayer::<2, 2>,
+             ChebyshevLayer::<4, 2>,
+             FourierFeaturesLayer::<8, 2>,
+             LinLayer::<4, 1>)
+}
+
+#[test]
+fn test_network_15() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![0.4f64, -0.5f64].as_slice()),
+        DVector::from_column_slice(vector![-0.2f64, 0.3f64].as_slice())];
+
+    check_network::<Test15Network>(10f64, &x_values);
+}
+
+#[neural_network]
+struct Test16Network {
+    layers: (LowRankLinLayer::<4, 5, 2>,
+             SigmaLayer::<5>,
+             LowRankLinLayer::<5, 2, 1>)
+}
+
+#[test]
+fn test_network_16() {
+    let x = DVector::from_column_slice(
+        vector![1f64, -0.5f64, 0.3f64, 2f64].as_slice());
+
+    check_network::<Test16Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test17Network {
+    layers: (LinLayer::<2, 3>,
+             TanhLayer::<3>,
+             LinLayer::<3, 3>,
+             SoftPlusLayer::<3>,
+             LinLayer::<3, 3>,
+             SwishLayer::<3>,
+             LinLayer::<3, 2>,
+             GaussianLayer::<2>)
+}
+
+#[test]
+fn test_network_17() {
+    let x = DVector::from_column_slice(
+        vector![0.8f64, -0.3f64].as_slice());
+
+    check_network::<Test17Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test18Network {
+    layers: (MaxoutLayer::<2, 3, 3>,
+             GLULayer::<3, 2>,
+             LinLayer::<2, 1>)
+}
+
+#[test]
+fn test_network_18() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![0.8f64, -0.3f64].as_slice()),
+        DVector::from_column_slice(vector![-1.2f64, 0.4f64].as_slice())];
+
+    check_network::<Test18Network>(5f64, &x_values);
+}
+
+#[neural_network]
+struct Test19Network {
+    layers: (LinLayer::<2, 3>,
+             L2NormalizeLayer::<3>,
+             ScaleShiftLayer::<3>,
+             LinLayer::<3, 1>)
+}
+
+#[test]
+fn test_network_19() {
+    let x = DVector::from_column_slice(
+        vector![0.8f64, -0.3f64].as_slice());
+
+    check_network::<Test19Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test20Network {
+    layers: (LinLayer::<2, 4>,
+             SigmaLayer::<4>,
+             LinLayer::<4, 6>,
+             MixtureDensityLayer::<2>)
+}
+
+#[test]
+fn test_network_20() {
+    let x = DVector::from_column_slice(
+        vector![0.8f64, -0.3f64].as_slice());
+
+    check_network::<Test20Network>(5f64, &[x]);
+}
+
+#[neural_network]
+struct Test21Network {
+    layers: (SplineLayer::<2, 3, 5>,
+             TanhLayer::<3>,
+             SplineLayer::<3, 1, 4>)
+}
+
+#[test]
+fn test_network_21() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![0.8f64, -0.3f64].as_slice()),
+        DVector::from_column_slice(vector![-0.1f64, 0.6f64].as_slice())];
+
+    check_network::<Test21Network>(5f64, &x_values);
+}
+
+#[neural_network]
+struct Test22Network {
+    layers: (LinLayer::<2, 4>,
+             LayerNormLayer::<4>,
+             ScaleShiftLayer::<4>,
+             SigmaLayer::<4>,
+             LinLayer::<4, 2>)
+}
+
+#[test]
+fn test_network_22() {
+    let x_values: Vec<DVector<f64>> = vec![
+        DVector::from_column_slice(vector![0.8f64, -0.3f64].as_slice()),
+        DVector::from_column_slice(vector![-1.5f64, 2f64].as_slice())];
+
+    check_network::<Test22Network>(5f64, &x_values);
+}
+
+#[neural_network]
+struct Mlp<const H: usize> {
+    layers: (LinLayer<1, H>,
+             SigmaLayer<H>,
+             LinLayer<H, 1>)
+}
+
+#[test]
+fn test_network_23() {
+    let x = DVector::from_column_slice(vector![0.7f64].as_slice());
+
+    assert_eq!(Mlp::<3>::PARAMS_CNT, 10);
+    assert_eq!(Mlp::<5>::PARAMS_CNT, 16);
+
+    check_network::<Mlp<3>>(5f64, &[x.clone()]);
+    check_network::<Mlp<5>>(5f64, &[x]);
+}
+
+#[derive(Network)]
+struct Test24Network {
+    layers: (LinLayer::<2, 3>,
+             TanhLayer::<3>,
+             LinLayer::<3, 1>)
+}
+
+#[test]
+fn test_network_24() {
+    let x = DVector::from_column_slice(
+        vector![0.8f64, -0.3f64].as_slice());
+
+    assert_eq!(Test24Network::PARAMS_CNT, 13);
+
+    check_network::<Test24Network>(5f64, &[x]);
+}
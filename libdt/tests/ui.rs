@@ -0,0 +1,9 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    // Having passing cases makes trybuild build rather than only
+    // check the failing ones, so that assertions in `const` blocks
+    // of generic functions are evaluated too.
+    t.pass("tests/ui/pass/*.rs");
+}
@@ -0,0 +1,8 @@
+use libdt_macros::neural_network;
+
+#[neural_network]
+struct EmptyNetwork {
+    layers: ()
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use libdt::layer::LinLayer;
+use libdt_macros::neural_network;
+
+#[neural_network]
+struct InvalidLayerNetwork {
+    layers: (LinLayer::<1, 1>,
+             [f64; 1])
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use libdt::layer::Chain;
+use libdt::layer::LinLayer;
+use libdt::layer::SigmaLayer;
+
+fn main() {
+    let _ = Chain::<LinLayer::<1, 10>, SigmaLayer::<8>>::new();
+}
@@ -0,0 +1,11 @@
+use libdt::layer::LinLayer;
+use libdt::layer::SigmaLayer;
+use libdt_macros::neural_network;
+
+#[neural_network]
+struct MismatchedNetwork {
+    layers: (LinLayer::<1, 10>,
+             SigmaLayer::<8>)
+}
+
+fn main() {}
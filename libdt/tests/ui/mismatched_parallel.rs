@@ -0,0 +1,7 @@
+use libdt::layer::LinLayer;
+use libdt::layer::Parallel;
+use libdt::layer::SigmaLayer;
+
+fn main() {
+    let _ = Parallel::<LinLayer::<3, 2>, SigmaLayer::<2>>::new();
+}
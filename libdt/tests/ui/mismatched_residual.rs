@@ -0,0 +1,6 @@
+use libdt::layer::LinLayer;
+use libdt::layer::Residual;
+
+fn main() {
+    let _ = Residual::<LinLayer::<3, 2>>::new();
+}
@@ -0,0 +1,9 @@
+use libdt::layer::LinLayer;
+use libdt_macros::neural_network;
+
+#[neural_network]
+enum EnumNetwork {
+    Layers(LinLayer::<1, 1>),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use libdt::layer::LinLayer;
+use libdt_macros::neural_network;
+
+#[neural_network]
+struct NotTupleNetwork {
+    layers: LinLayer::<1, 1>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use libdt::layer::Chain;
+use libdt::layer::LinLayer;
+use libdt::layer::Parallel;
+use libdt::layer::Residual;
+use libdt::layer::SigmaLayer;
+
+fn main() {
+    let _ = Chain::<LinLayer::<1, 8>, SigmaLayer::<8>>::new();
+    let _ = Residual::<LinLayer::<3, 3>>::new();
+    let _ = Parallel::<LinLayer::<3, 2>, SigmaLayer::<3>>::new();
+}
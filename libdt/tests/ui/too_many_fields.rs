@@ -0,0 +1,10 @@
+use libdt::layer::LinLayer;
+use libdt_macros::neural_network;
+
+#[neural_network]
+struct TooManyFieldsNetwork {
+    layers: (LinLayer::<1, 1>,),
+    rate: f64,
+}
+
+fn main() {}
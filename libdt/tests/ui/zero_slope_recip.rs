@@ -0,0 +1,7 @@
+use libdt::layer::Layer;
+use libdt::layer::LeakyReLULayer;
+use nalgebra::DVector;
+
+fn main() {
+    let _ = LeakyReLULayer::<3, 0>::eval(&[], DVector::zeros(3));
+}